@@ -11,7 +11,7 @@ use indicatif::{ProgressBar, ProgressStyle};
 use rayon::prelude::*;
 
 /// Write the analysis data to a subdirectory under target/ with this name.
-const SUBDIR: &str = "rsbrowse";
+pub(crate) const SUBDIR: &str = "rsbrowse";
 
 const EMPTY_ID: &rustdoc_types::Id = &rustdoc_types::Id(u32::MAX);
 static EMPTY_STRING: String = String::new();
@@ -26,6 +26,22 @@ pub static EMPTY_ITEM_ID: ItemId<'static> = ItemId(
 
 pub struct Analysis {
     pub crates: HashMap<String, rustdoc_types::Crate>,
+    /// For each crate, maps item IDs to the ID of the item they were declared in (e.g. fields to
+    /// their struct, methods to their impl, impls to their type, and items to their module).
+    parents: HashMap<String, HashMap<rustdoc_types::Id, rustdoc_types::Id>>,
+    /// Maps the full path of each trait which is a supertrait of some other trait, to the crate
+    /// names and IDs of those other traits.
+    subtraits: HashMap<Vec<String>, Vec<(String, rustdoc_types::Id)>>,
+    /// Maps the names of primitive types, like `str`, to the crate names and IDs of the items
+    /// documenting them, each of which lists the type's impls in its crate.
+    primitives: HashMap<String, Vec<(String, rustdoc_types::Id)>>,
+    /// Maps the full paths of types to the crate names and IDs of the functions returning them,
+    /// directly or in an `Option`, `Result`, or `Box`.
+    constructors: HashMap<Vec<String>, Vec<(String, rustdoc_types::Id)>>,
+    /// Maps each crate's name to the names of the other loaded crates it refers to, sorted.
+    dependencies: HashMap<String, Vec<String>>,
+    /// Maps each crate's name to the names of the other loaded crates which refer to it, sorted.
+    dependents: HashMap<String, Vec<String>>,
 }
 
 impl Analysis {
@@ -74,9 +90,15 @@ impl Analysis {
     }
 
     pub fn load(workspace_path: impl Into<PathBuf>) -> anyhow::Result<Self> {
-        let root = json_root(&workspace_path.into());
+        Self::load_json_dir(json_root(&workspace_path.into()))
+    }
+
+    /// Load all the rustdoc JSON files in the given directory, such as the one that `generate`
+    /// writes to, or one populated some other way.
+    pub fn load_json_dir(root: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let root = root.as_ref();
         let mut paths = vec![];
-        for res in fs::read_dir(root)? {
+        for res in fs::read_dir(root).with_context(|| root.display().to_string())? {
             let entry = res?;
             if entry.file_name().as_encoded_bytes().ends_with(b".json") {
                 let path = entry.path();
@@ -121,10 +143,70 @@ impl Analysis {
             })
             .collect::<anyhow::Result<HashMap<_, _>>>()?;
 
-        Ok(Self { crates })
+        let parents: HashMap<_, _> = crates
+            .par_iter()
+            .map(|(name, crate_)| (name.clone(), parent_map(crate_)))
+            .collect();
+
+        let mut subtraits: HashMap<_, Vec<_>> = HashMap::new();
+        for (name, crate_) in &crates {
+            for (super_path, sub_id) in subtrait_pairs(crate_) {
+                subtraits
+                    .entry(super_path)
+                    .or_default()
+                    .push((name.clone(), sub_id));
+            }
+        }
+
+        let mut primitives: HashMap<_, Vec<_>> = HashMap::new();
+        for (name, crate_) in &crates {
+            for (id, item) in &crate_.index {
+                if let rustdoc_types::ItemEnum::Primitive(p) = &item.inner {
+                    primitives
+                        .entry(p.name.clone())
+                        .or_default()
+                        .push((name.clone(), *id));
+                }
+            }
+        }
+
+        let mut constructors: HashMap<_, Vec<_>> = HashMap::new();
+        for (name, crate_) in &crates {
+            for (type_path, fn_id) in constructor_pairs(crate_, &parents[name]) {
+                constructors
+                    .entry(type_path)
+                    .or_default()
+                    .push((name.clone(), fn_id));
+            }
+        }
+
+        let dependencies = crates
+            .iter()
+            .map(|(name, crate_)| (name.clone(), crate_dependencies(&crates, name, crate_)))
+            .collect::<HashMap<_, _>>();
+        let mut dependents: HashMap<_, Vec<_>> = HashMap::new();
+        for (name, deps) in &dependencies {
+            for dep in deps {
+                dependents
+                    .entry(dep.clone())
+                    .or_default()
+                    .push(name.clone());
+            }
+        }
+        dependents.values_mut().for_each(|names| names.sort());
+
+        Ok(Self {
+            crates,
+            parents,
+            subtraits,
+            primitives,
+            constructors,
+            dependencies,
+            dependents,
+        })
     }
 
-    pub fn crate_ids(&self) -> impl Iterator<Item = ItemId> + '_ {
+    pub fn crate_ids(&self) -> impl Iterator<Item = ItemId<'_>> + '_ {
         self.crates
             .values()
             .flat_map(|crate_| &crate_.index)
@@ -137,6 +219,27 @@ impl Analysis {
             })
     }
 
+    /// Whether the named crate is a member of the workspace, as opposed to a dependency or part of
+    /// the standard library. Cargo only gives rustdoc relative source paths for workspace members,
+    /// so this looks at the path of the crate root.
+    pub fn is_workspace_crate(&self, name: &str) -> bool {
+        self.crates
+            .get(name)
+            .and_then(|crate_| crate_.index.get(&crate_.root))
+            .and_then(|root| root.span.as_ref())
+            .is_some_and(|span| span.filename.is_relative())
+    }
+
+    /// Names of the other loaded crates which the named crate refers to.
+    pub fn crate_dependencies(&self, name: &str) -> Vec<&String> {
+        self.dependencies.get(name).into_iter().flatten().collect()
+    }
+
+    /// Names of the other loaded crates which refer to the named crate.
+    pub fn crate_dependents(&self, name: &str) -> Vec<&String> {
+        self.dependents.get(name).into_iter().flatten().collect()
+    }
+
     pub fn items<'a, 'b>(
         &'a self,
         parent_id: &'b ItemId<'a>,
@@ -158,6 +261,24 @@ impl Analysis {
             }
         };
 
+        // A `use` has the children of whatever it imports. A glob import of an enum only brings
+        // its variants into scope though, not its impls.
+        let mut glob_import = false;
+        let (parent_id, parent) = match parent {
+            Some(parent) => match self.resolve_use(&parent_id, parent) {
+                Some((target_id, target)) => {
+                    glob_import =
+                        matches!(&parent.inner, rustdoc_types::ItemEnum::Use(u) if u.is_glob);
+                    (target_id, Some(target))
+                }
+                None => (parent_id, Some(parent)),
+            },
+            None => (parent_id, None),
+        };
+
+        // Methods a trait impl inherits from the trait, which may be in a different crate.
+        let mut inherited = vec![];
+
         // Collect (crate-local) IDs of children depending on the kind of parent it is.
         let children: Vec<&'a rustdoc_types::Id> = if let Some(parent) = parent {
             use rustdoc_types::ItemEnum::*;
@@ -165,7 +286,8 @@ impl Analysis {
                 _ if parent_id == EMPTY_ITEM_ID => vec![],
                 Module(m) => m.items.iter().collect(),
                 ExternCrate { .. } => vec![],
-                Use(_) => vec![],
+                Use(_) => vec![], // unresolvable
+                Enum(e) if glob_import => e.variants.iter().collect(),
                 Union(u) => u.fields.iter().chain(&u.impls).collect(),
                 Struct(s) => {
                     let fields = match &s.kind {
@@ -193,6 +315,7 @@ impl Analysis {
                 }
                 TraitAlias(_) => vec![],
                 Impl(i) => {
+                    inherited = self.inherited_methods(&parent_id, i);
                     i.items
                         .iter()
                         // Add a reference to the trait itself too if it's not an inherent impl:
@@ -217,6 +340,57 @@ impl Analysis {
         children
             .into_iter()
             .filter_map(move |id| self.get_item(parent_id.crate_sibling(id)))
+            .chain(inherited)
+    }
+
+    /// Look up the trait that an impl is of, if it's a trait impl.
+    pub fn impl_trait<'a>(
+        &'a self,
+        impl_id: &ItemId<'a>,
+        impl_: &'a rustdoc_types::Impl,
+    ) -> Option<(ItemId<'a>, &'a rustdoc_types::Trait)> {
+        let trait_ = impl_.trait_.as_ref()?;
+        match self.get_item(impl_id.crate_sibling(&trait_.id))? {
+            (
+                trait_id,
+                Item::Item(rustdoc_types::Item {
+                    inner: rustdoc_types::ItemEnum::Trait(t),
+                    ..
+                }),
+            ) => Some((trait_id, t)),
+            _ => None,
+        }
+    }
+
+    /// For a trait impl, get the trait's provided methods which the impl doesn't override.
+    pub fn inherited_methods<'a>(
+        &'a self,
+        impl_id: &ItemId<'a>,
+        impl_: &'a rustdoc_types::Impl,
+    ) -> Vec<(ItemId<'a>, Item<'a>)> {
+        let Some((trait_id, trait_)) = self.impl_trait(impl_id, impl_) else {
+            return vec![];
+        };
+        // provided_trait_methods isn't reliable about excluding overridden methods.
+        let overridden = impl_
+            .items
+            .iter()
+            .filter_map(|id| match self.get_item(impl_id.crate_sibling(id)) {
+                Some((_, Item::Item(item))) => item.name.as_ref(),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        trait_
+            .items
+            .iter()
+            .filter_map(|id| self.get_item(trait_id.crate_sibling(id)))
+            .filter(|(_, item)| match item {
+                Item::Item(item) => item.name.as_ref().is_some_and(|name| {
+                    impl_.provided_trait_methods.contains(name) && !overridden.contains(&name)
+                }),
+                Item::Root => false,
+            })
+            .collect()
     }
 
     pub fn get_item<'a>(&'a self, id: ItemId<'a>) -> Option<(ItemId<'a>, Item<'a>)> {
@@ -271,6 +445,277 @@ impl Analysis {
         }
     }
 
+    /// If the item is a `use`, look up the item it imports. This may be in a different crate. For
+    /// glob imports, this is the module or enum whose contents are imported.
+    pub fn resolve_use<'a>(
+        &'a self,
+        id: &ItemId<'a>,
+        item: &'a rustdoc_types::Item,
+    ) -> Option<(ItemId<'a>, &'a rustdoc_types::Item)> {
+        let rustdoc_types::ItemEnum::Use(u) = &item.inner else {
+            return None;
+        };
+        match self.get_item(id.crate_sibling(u.id.as_ref()?))? {
+            (target_id, Item::Item(target)) => Some((target_id, target)),
+            (_, Item::Root) => None,
+        }
+    }
+
+    /// Lists the items reachable through public paths in the given module: its public items, with
+    /// `pub use` re-exports replaced by the items they refer to, and glob re-exports expanded.
+    /// Items re-exported under a name come with that name. Re-exports that can't be resolved are
+    /// listed as the `use` itself.
+    pub fn public_api_items<'a>(
+        &'a self,
+        module_id: &ItemId<'a>,
+        module: &'a rustdoc_types::Module,
+        visited: &mut Vec<ItemId<'a>>,
+    ) -> Vec<(ItemId<'a>, &'a rustdoc_types::Item, Option<&'a str>)> {
+        if visited.contains(module_id) {
+            // Glob re-exports can be cyclic.
+            return vec![];
+        }
+        visited.push(module_id.clone());
+
+        let mut items = vec![];
+        let mut globs = vec![];
+        let mut names = vec![];
+        for (id, item) in
+            module
+                .items
+                .iter()
+                .filter_map(|id| match self.get_item(module_id.crate_sibling(id)) {
+                    Some((id, Item::Item(item))) => Some((id, item)),
+                    _ => None,
+                })
+        {
+            if item.visibility != rustdoc_types::Visibility::Public || is_doc_hidden(item) {
+                continue;
+            }
+            let rustdoc_types::ItemEnum::Use(u) = &item.inner else {
+                items.push((id, item, None));
+                names.extend(item.name.as_deref());
+                continue;
+            };
+            match self.resolve_use(&id, item) {
+                Some((target_id, target)) => {
+                    if u.is_glob {
+                        globs.push((target_id, target));
+                    } else {
+                        items.push((target_id, target, Some(u.name.as_str())));
+                        names.push(&u.name);
+                    }
+                }
+                None => items.push((id, item, None)),
+            }
+        }
+
+        // Items named explicitly take precedence over ones brought in by globs.
+        for (glob_id, glob) in globs {
+            let glob_items = match &glob.inner {
+                rustdoc_types::ItemEnum::Module(m) => self.public_api_items(&glob_id, m, visited),
+                rustdoc_types::ItemEnum::Enum(e) => e
+                    .variants
+                    .iter()
+                    .filter_map(|id| match self.get_item(glob_id.crate_sibling(id)) {
+                        Some((id, Item::Item(item))) => Some((id, item, None)),
+                        _ => None,
+                    })
+                    .collect(),
+                _ => vec![],
+            };
+            for (id, item, rename) in glob_items {
+                let name = rename.or(item.name.as_deref());
+                if name.is_some_and(|name| names.contains(&name)) {
+                    continue;
+                }
+                items.push((id, item, rename));
+            }
+        }
+
+        items
+    }
+
+    /// What kind of item an ID refers to, from its crate's summary of it.
+    pub fn get_kind(&self, id: &ItemId) -> Option<rustdoc_types::ItemKind> {
+        Some(self.crates.get(id.crate_name())?.paths.get(id.1)?.kind)
+    }
+
+    /// Get the ID of the item that the given item was declared in.
+    pub fn parent<'a>(&'a self, id: &ItemId<'a>) -> Option<ItemId<'a>> {
+        let parent = self.parents.get(id.crate_name())?.get(id.1)?;
+        Some(id.crate_sibling(parent))
+    }
+
+    /// Get the ID of the module that the given item was declared in, directly or indirectly.
+    pub fn containing_module<'a>(&'a self, id: &ItemId<'a>) -> Option<ItemId<'a>> {
+        let mut id = self.parent(id)?;
+        loop {
+            if let Some((_, Item::Item(item))) = self.get_item(id.clone()) {
+                if matches!(item.inner, rustdoc_types::ItemEnum::Module(_)) {
+                    return Some(id);
+                }
+            }
+            id = self.parent(&id)?;
+        }
+    }
+
+    /// Look up an item defined in one of the loaded crates by its full path, starting with the
+    /// crate name.
+    pub fn find_path(&self, path: &[&str]) -> Option<ItemId<'_>> {
+        let (name, crate_) = self.crates.get_key_value(*path.first()?)?;
+        if path.len() == 1 {
+            return Some(ItemId::crate_root(CrateId { name }));
+        }
+        crate_
+            .paths
+            .iter()
+            .find(|(_, summary)| summary.crate_id == 0 && summary.path == path)
+            .map(|(id, _)| ItemId(CrateId { name }, id))
+    }
+
+    /// Get the `macro_rules!` and procedural macros defined anywhere in the named crate.
+    pub fn macros(&self, crate_name: &str) -> Vec<ItemId<'_>> {
+        let Some((name, crate_)) = self.crates.get_key_value(crate_name) else {
+            return vec![];
+        };
+        crate_
+            .index
+            .iter()
+            .filter(|(_, item)| {
+                item.crate_id == 0
+                    && matches!(
+                        item.inner,
+                        rustdoc_types::ItemEnum::Macro(_) | rustdoc_types::ItemEnum::ProcMacro(_)
+                    )
+            })
+            .map(|(id, _)| ItemId(CrateId { name }, id))
+            .collect()
+    }
+
+    /// Get the traits in any loaded crate which have the given trait as a supertrait.
+    pub fn subtraits<'a>(&'a self, trait_id: &ItemId<'a>) -> Vec<ItemId<'a>> {
+        let Some(path) = self.get_path(trait_id.clone(), "subtraits") else {
+            return vec![];
+        };
+        self.subtraits
+            .get(path)
+            .into_iter()
+            .flatten()
+            .filter_map(|(crate_name, id)| {
+                let (name, _) = self.crates.get_key_value(crate_name)?;
+                Some(ItemId(CrateId { name }, id))
+            })
+            .collect()
+    }
+
+    /// Get the items in any loaded crate which document the primitive type with the given name,
+    /// like `str` or `slice`.
+    pub fn primitives<'a>(&'a self, name: &str) -> Vec<ItemId<'a>> {
+        self.primitives
+            .get(name)
+            .into_iter()
+            .flatten()
+            .filter_map(|(crate_name, id)| {
+                let (name, _) = self.crates.get_key_value(crate_name)?;
+                Some(ItemId(CrateId { name }, id))
+            })
+            .collect()
+    }
+
+    /// Get the functions and methods in any loaded crate which return the given type, directly or
+    /// in an `Option`, `Result`, or `Box`. This includes methods returning `Self` in its impls,
+    /// like `Default::default`, `From::from`, and `FromStr::from_str`.
+    pub fn constructors<'a>(&'a self, type_id: &ItemId<'a>) -> Vec<ItemId<'a>> {
+        let Some(type_path) = self.get_path(type_id.clone(), "constructors") else {
+            return vec![];
+        };
+        self.constructors
+            .get(type_path)
+            .into_iter()
+            .flatten()
+            .filter_map(|(crate_name, id)| {
+                let (name, _) = self.crates.get_key_value(crate_name)?;
+                Some(ItemId(CrateId { name }, id))
+            })
+            .collect()
+    }
+
+    /// Whether a path used in the crate of the given item refers to the item at the given full
+    /// path.
+    pub fn is_path_to(
+        &self,
+        from: &ItemId,
+        path: &rustdoc_types::Path,
+        type_path: &[impl AsRef<str>],
+    ) -> bool {
+        // Check the name first, because looking up the path is slower.
+        path.path.rsplit("::").next() == type_path.last().map(AsRef::as_ref)
+            && self
+                .crates
+                .get(from.crate_name())
+                .and_then(|crate_| crate_.paths.get(&path.id))
+                .is_some_and(|summary| {
+                    summary
+                        .path
+                        .iter()
+                        .map(String::as_str)
+                        .eq(type_path.iter().map(AsRef::as_ref))
+                })
+    }
+
+    /// Find the ID of an item borrowed from this analysis, by looking for the crate it's in.
+    pub fn find_item_id<'a>(&'a self, item: &'a rustdoc_types::Item) -> Option<ItemId<'a>> {
+        self.crates.iter().find_map(|(name, crate_)| {
+            crate_
+                .index
+                .get_key_value(&item.id)
+                .filter(|(_, indexed)| std::ptr::eq(*indexed, item))
+                .map(|(id, _)| ItemId(CrateId { name }, id))
+        })
+    }
+
+    /// An item's name, preceded by the names of the types, traits and variants it's declared in,
+    /// e.g. "Type::method" or "Enum::Variant::field".
+    pub fn name_in_module(&self, id: &ItemId, item: &rustdoc_types::Item) -> String {
+        let mut names = vec![item.name.as_deref().unwrap_or("<unnamed>")];
+        let mut id = id.clone();
+        while let Some(parent_id) = self.parent(&id) {
+            let Some((parent_id, Item::Item(parent))) = self.get_item(parent_id) else {
+                break;
+            };
+            match &parent.inner {
+                rustdoc_types::ItemEnum::Module(_) => break,
+                rustdoc_types::ItemEnum::Impl(i) => {
+                    if let rustdoc_types::Type::ResolvedPath(path) = &i.for_ {
+                        names.push(path.path.rsplit("::").next().unwrap());
+                    }
+                    // The type the impl is for isn't necessarily its parent, so stop here.
+                    break;
+                }
+                _ => {
+                    names.push(parent.name.as_deref().unwrap_or("<unnamed>"));
+                    id = parent_id;
+                }
+            }
+        }
+        names.reverse();
+        names.join("::")
+    }
+
+    /// An item's full path, including ones like methods and fields which don't have their own
+    /// entry in the crate's paths.
+    pub fn full_name(&self, id: &ItemId, item: &rustdoc_types::Item) -> String {
+        let name = self.name_in_module(id, item);
+        let module_path = self
+            .containing_module(id)
+            .and_then(|module| self.get_path(module, "module"));
+        match module_path {
+            Some(path) => format!("{}::{name}", path.join("::")),
+            None => name,
+        }
+    }
+
     pub fn get_path<'a>(&'a self, id: ItemId<'a>, name_hint: &str) -> Option<&'a [String]> {
         if id == EMPTY_ITEM_ID {
             return None;
@@ -288,12 +733,243 @@ impl Analysis {
     }
 }
 
+/// Names of the other loaded crates which the given crate refers to, sorted.
+fn crate_dependencies(
+    crates: &HashMap<String, rustdoc_types::Crate>,
+    name: &str,
+    crate_: &rustdoc_types::Crate,
+) -> Vec<String> {
+    let mut deps = crate_
+        .external_crates
+        .values()
+        .map(|ext| &ext.name)
+        .filter(|dep| *dep != name && crates.contains_key(*dep))
+        .cloned()
+        .collect::<Vec<_>>();
+    deps.sort();
+    deps.dedup();
+    deps
+}
+
 fn parse_json(p: &Path) -> anyhow::Result<rustdoc_types::Crate> {
     let f = File::open(p)?;
     let data = serde_json::from_reader(BufReader::new(f))?;
     Ok(data)
 }
 
+/// Build a map from item IDs to the ID of the item they were declared in, for one crate.
+fn parent_map(crate_: &rustdoc_types::Crate) -> HashMap<rustdoc_types::Id, rustdoc_types::Id> {
+    use rustdoc_types::ItemEnum::*;
+    let mut parents = HashMap::new();
+    for (id, item) in &crate_.index {
+        let children: Vec<&rustdoc_types::Id> = match &item.inner {
+            Module(m) => m.items.iter().collect(),
+            Union(u) => u.fields.iter().chain(&u.impls).collect(),
+            Struct(s) => {
+                let fields: Vec<_> = match &s.kind {
+                    rustdoc_types::StructKind::Unit => vec![],
+                    rustdoc_types::StructKind::Tuple(t) => t.iter().flatten().collect(),
+                    rustdoc_types::StructKind::Plain { fields, .. } => fields.iter().collect(),
+                };
+                fields.into_iter().chain(&s.impls).collect()
+            }
+            Enum(e) => e.variants.iter().chain(&e.impls).collect(),
+            Variant(v) => match &v.kind {
+                rustdoc_types::VariantKind::Plain => vec![],
+                rustdoc_types::VariantKind::Tuple(t) => t.iter().flatten().collect(),
+                rustdoc_types::VariantKind::Struct { fields, .. } => fields.iter().collect(),
+            },
+            Trait(t) => t.items.iter().collect(),
+            Impl(i) => i.items.iter().collect(),
+            Primitive(p) => p.impls.iter().collect(),
+            _ => vec![],
+        };
+        for child in children {
+            // Impls are listed under every local type they mention, and blanket impls under many
+            // types, so prefer the type an impl is for, or else keep the first one seen.
+            let is_for = crate_.index.get(child).is_some_and(|child| {
+                matches!(&child.inner, Impl(i)
+                    if matches!(&i.for_, rustdoc_types::Type::ResolvedPath(p) if p.id == *id))
+            });
+            if is_for {
+                parents.insert(*child, *id);
+            } else {
+                parents.entry(*child).or_insert(*id);
+            }
+        }
+    }
+    parents
+}
+
+/// Whether an item is visible outside its crate, assuming its parent is. Impls, variants,
+/// variants' fields, and items of traits and trait impls don't have their own visibility, and are
+/// exactly as visible as their parent.
+pub fn is_public(item: &rustdoc_types::Item, parent: Option<&rustdoc_types::Item>) -> bool {
+    use rustdoc_types::ItemEnum::*;
+    match item.visibility {
+        rustdoc_types::Visibility::Public => true,
+        rustdoc_types::Visibility::Crate | rustdoc_types::Visibility::Restricted { .. } => false,
+        rustdoc_types::Visibility::Default => {
+            matches!(item.inner, Impl(_) | Variant(_))
+                || parent.is_some_and(|p| match &p.inner {
+                    Trait(_) | Variant(_) => true,
+                    Impl(i) => i.trait_.is_some(),
+                    _ => false,
+                })
+        }
+    }
+}
+
+pub fn is_doc_hidden(item: &rustdoc_types::Item) -> bool {
+    item.attrs.iter().any(|attr| attr == "#[doc(hidden)]")
+}
+
+/// A short name for what kind of item something is, like "struct" or "fn".
+pub fn kind_name(item: &rustdoc_types::Item) -> &'static str {
+    use rustdoc_types::ItemEnum::*;
+    match &item.inner {
+        Module(_) => "mod",
+        ExternCrate { .. } => "extern crate",
+        Use(_) => "use",
+        Struct(_) => "struct",
+        StructField(_) => "field",
+        Enum(_) => "enum",
+        Variant(_) => "variant",
+        Union(_) => "union",
+        Trait(_) => "trait",
+        TraitAlias(_) => "trait alias",
+        Impl(_) => "impl",
+        Function(_) => "fn",
+        TypeAlias(_) | AssocType { .. } => "type",
+        Constant { .. } | AssocConst { .. } => "const",
+        Static(_) => "static",
+        Macro(_) => "macro",
+        ProcMacro(_) => "proc macro",
+        Primitive(_) => "primitive",
+        ExternType => "extern type",
+    }
+}
+
+/// The bounds on a trait's `Self` type: the ones after the colon in its declaration, and ones in
+/// its where clause.
+pub fn supertrait_bounds(t: &rustdoc_types::Trait) -> Vec<&rustdoc_types::GenericBound> {
+    let where_bounds = t
+        .generics
+        .where_predicates
+        .iter()
+        .flat_map(|pred| match pred {
+            rustdoc_types::WherePredicate::BoundPredicate {
+                type_: rustdoc_types::Type::Generic(name),
+                bounds,
+                ..
+            } if name == "Self" => &bounds[..],
+            _ => &[],
+        });
+    t.bounds
+        .iter()
+        .chain(where_bounds)
+        .filter(|b| matches!(b, rustdoc_types::GenericBound::TraitBound { .. }))
+        .collect()
+}
+
+/// Pairs of the path of each supertrait of a trait in the given crate, and the trait's ID.
+fn subtrait_pairs(crate_: &rustdoc_types::Crate) -> Vec<(Vec<String>, rustdoc_types::Id)> {
+    let mut pairs = vec![];
+    for (id, item) in &crate_.index {
+        let rustdoc_types::ItemEnum::Trait(t) = &item.inner else {
+            continue;
+        };
+        if item.crate_id != 0 {
+            continue;
+        }
+        for bound in supertrait_bounds(t) {
+            if let rustdoc_types::GenericBound::TraitBound { trait_, .. } = bound {
+                if let Some(summary) = crate_.paths.get(&trait_.id) {
+                    pairs.push((summary.path.clone(), *id));
+                }
+            }
+        }
+    }
+    pairs
+}
+
+/// Pairs of the full paths of types and the IDs of functions in the crate which return them.
+fn constructor_pairs(
+    crate_: &rustdoc_types::Crate,
+    parents: &HashMap<rustdoc_types::Id, rustdoc_types::Id>,
+) -> Vec<(Vec<String>, rustdoc_types::Id)> {
+    let mut pairs = vec![];
+    for (id, item) in &crate_.index {
+        let rustdoc_types::ItemEnum::Function(f) = &item.inner else {
+            continue;
+        };
+        let Some(output) = &f.sig.output else {
+            continue;
+        };
+        if item.crate_id != 0 {
+            continue;
+        }
+        let self_ty = parents
+            .get(id)
+            .and_then(|parent| crate_.index.get(parent))
+            .and_then(|parent| match &parent.inner {
+                rustdoc_types::ItemEnum::Impl(i) => Some(&i.for_),
+                _ => None,
+            });
+        let mut paths = vec![];
+        returned_type_paths(crate_, output, self_ty, &mut paths);
+        paths.dedup();
+        pairs.extend(paths.into_iter().map(|path| (path.to_owned(), *id)));
+    }
+    pairs
+}
+
+/// The full paths of the type, and of what it wraps if it's an `Option`, `Result`, or `Box`.
+/// `self_ty` is what `Self` is, for methods.
+fn returned_type_paths<'a>(
+    crate_: &'a rustdoc_types::Crate,
+    ty: &'a rustdoc_types::Type,
+    self_ty: Option<&'a rustdoc_types::Type>,
+    paths: &mut Vec<&'a [String]>,
+) {
+    match ty {
+        rustdoc_types::Type::ResolvedPath(p) => {
+            let name = p.path.rsplit("::").next();
+            if matches!(name, Some("Option" | "Result" | "Box")) {
+                if let Some(rustdoc_types::GenericArgs::AngleBracketed { args, .. }) =
+                    p.args.as_deref()
+                {
+                    if let Some(rustdoc_types::GenericArg::Type(inner)) = args.first() {
+                        returned_type_paths(crate_, inner, self_ty, paths);
+                    }
+                }
+            }
+            paths.extend(crate_.paths.get(&p.id).map(|summary| &summary.path[..]));
+        }
+        rustdoc_types::Type::Generic(g) if g == "Self" => {
+            if let Some(self_ty @ rustdoc_types::Type::ResolvedPath(_)) = self_ty {
+                returned_type_paths(crate_, self_ty, None, paths);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// The name rustdoc documents a primitive type under, if the type is one.
+pub fn primitive_name(ty: &rustdoc_types::Type) -> Option<&str> {
+    match ty {
+        rustdoc_types::Type::Primitive(name) => Some(name),
+        rustdoc_types::Type::Slice(_) => Some("slice"),
+        rustdoc_types::Type::Array { .. } => Some("array"),
+        rustdoc_types::Type::Tuple(types) if !types.is_empty() => Some("tuple"),
+        rustdoc_types::Type::Tuple(_) => Some("unit"),
+        rustdoc_types::Type::BorrowedRef { .. } => Some("reference"),
+        rustdoc_types::Type::RawPointer { .. } => Some("pointer"),
+        rustdoc_types::Type::FunctionPointer(_) => Some("fn"),
+        _ => None,
+    }
+}
+
 pub fn type_ids(ty: &rustdoc_types::Type) -> Vec<&rustdoc_types::Id> {
     use rustdoc_types::Type::*;
     match ty {
@@ -330,8 +1006,13 @@ pub fn type_ids(ty: &rustdoc_types::Type) -> Vec<&rustdoc_types::Id> {
     }
 }
 
+/// Where rsbrowse writes its files for a workspace: the rustdoc output, and anything exported.
+pub fn output_dir(workspace_path: &Path) -> PathBuf {
+    workspace_path.join("target").join(SUBDIR)
+}
+
 fn json_root(workspace_path: &Path) -> PathBuf {
-    workspace_path.join("target").join(SUBDIR).join("doc")
+    output_dir(workspace_path).join("doc")
 }
 
 pub fn get_stdlib_analysis_path(toolchain: Option<&str>) -> anyhow::Result<PathBuf> {
@@ -375,7 +1056,7 @@ fn copy_stdlib_json(workspace_path: &Path, toolchain: Option<&str>) -> anyhow::R
         let entry = res?;
         if entry.file_name().as_encoded_bytes().ends_with(b".json") {
             let src_path = entry.path();
-            println!("copying {:?}", entry.file_name());
+            info!("copying {:?}", entry.file_name());
             fs::copy(&src_path, dst.join(entry.file_name()))
                 .with_context(|| format!("copy {src_path:?}"))?;
         }
@@ -383,12 +1064,12 @@ fn copy_stdlib_json(workspace_path: &Path, toolchain: Option<&str>) -> anyhow::R
     Ok(())
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CrateId<'a> {
     pub name: &'a String,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ItemId<'a>(CrateId<'a>, &'a rustdoc_types::Id);
 
 impl<'a> ItemId<'a> {
@@ -400,9 +1081,18 @@ impl<'a> ItemId<'a> {
         self.0.name
     }
 
+    pub fn crate_id(&self) -> CrateId<'a> {
+        self.0.clone()
+    }
+
     pub fn crate_sibling(&self, other_id: &'a rustdoc_types::Id) -> Self {
         Self(CrateId { name: self.0.name }, other_id)
     }
+
+    /// The item's ID within its crate.
+    pub fn id(&self) -> &'a rustdoc_types::Id {
+        self.1
+    }
 }
 
 #[allow(clippy::large_enum_variant)]
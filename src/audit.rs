@@ -0,0 +1,231 @@
+//! Finding the unsafe code in the workspace's crates, for review.
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use crate::analysis::{Analysis, CrateId, Item, ItemId};
+use crate::browser_rustdoc::type_label;
+
+/// Auto traits which are unsafe to implement, for when the standard library's analysis isn't
+/// loaded and this can't be looked up.
+const UNSAFE_AUTO_TRAITS: &[&[&str]] = &[&["core", "marker", "Send"], &["core", "marker", "Sync"]];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum UnsafeKind {
+    Function,
+    ExternFunction,
+    Trait,
+    Impl,
+    StaticMut,
+    ExternStatic,
+}
+
+impl UnsafeKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            UnsafeKind::Function => "unsafe fn",
+            UnsafeKind::ExternFunction => "extern fn",
+            UnsafeKind::Trait => "unsafe trait",
+            UnsafeKind::Impl => "unsafe impl",
+            UnsafeKind::StaticMut => "static mut",
+            UnsafeKind::ExternStatic => "extern static",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UnsafeItem<'a> {
+    pub id: ItemId<'a>,
+    pub item: &'a rustdoc_types::Item,
+    pub kind: UnsafeKind,
+    /// Full path of the module the item is in.
+    pub module: String,
+    /// What the item is, e.g. "unsafe fn foo" or "unsafe impl Send for Foo".
+    pub label: String,
+}
+
+/// The unsafe items of each workspace crate, found once so they can be counted and listed without
+/// searching the crate again.
+#[derive(Debug, Default)]
+pub struct UnsafeIndex {
+    /// IDs of the unsafe items, sorted like `unsafe_items`, by crate name.
+    items: HashMap<String, Vec<rustdoc_types::Id>>,
+}
+
+impl UnsafeIndex {
+    pub fn build(analysis: &Analysis) -> Self {
+        let items = analysis
+            .crates
+            .keys()
+            .filter(|name| analysis.is_workspace_crate(name))
+            .map(|name| {
+                let ids = find_unsafe(analysis, name)
+                    .into_iter()
+                    .map(|(_, id)| *id)
+                    .collect();
+                (name.clone(), ids)
+            })
+            .collect();
+        Self { items }
+    }
+
+    /// How many unsafe items a crate has.
+    pub fn count(&self, crate_name: &str) -> usize {
+        self.items.get(crate_name).map_or(0, Vec::len)
+    }
+
+    /// The unsafe items of a crate, sorted by module.
+    pub fn unsafe_items<'a>(
+        &self,
+        analysis: &'a Analysis,
+        crate_name: &str,
+    ) -> Vec<UnsafeItem<'a>> {
+        let (Some(ids), Some((name, crate_))) = (
+            self.items.get(crate_name),
+            analysis.crates.get_key_value(crate_name),
+        ) else {
+            return vec![];
+        };
+        let root = ItemId::crate_root(CrateId { name });
+        ids.iter()
+            .filter_map(|id| {
+                let (id, item) = crate_.index.get_key_value(id)?;
+                unsafe_item(analysis, root.crate_sibling(id), item)
+            })
+            .collect()
+    }
+}
+
+/// Find the unsafe functions, traits, impls, and mutable or extern statics declared in a crate,
+/// sorted by module.
+pub fn unsafe_items<'a>(analysis: &'a Analysis, crate_name: &str) -> Vec<UnsafeItem<'a>> {
+    find_unsafe(analysis, crate_name)
+        .into_iter()
+        .map(|(item, _)| item)
+        .collect()
+}
+
+/// The unsafe items declared in a crate, sorted by module, with their IDs.
+fn find_unsafe<'a>(
+    analysis: &'a Analysis,
+    crate_name: &str,
+) -> Vec<(UnsafeItem<'a>, &'a rustdoc_types::Id)> {
+    let Some((name, crate_)) = analysis.crates.get_key_value(crate_name) else {
+        return vec![];
+    };
+    let root = ItemId::crate_root(CrateId { name });
+    let mut items = crate_
+        .index
+        .iter()
+        // Leave out things rustdoc synthesized or copied from elsewhere, which have no source.
+        .filter(|(_, item)| item.crate_id == 0 && item.span.is_some())
+        .filter_map(|(id, item)| Some((unsafe_item(analysis, root.crate_sibling(id), item)?, id)))
+        .collect::<Vec<_>>();
+    items.sort_by(|(a, _), (b, _)| a.module.cmp(&b.module).then_with(|| a.label.cmp(&b.label)));
+    items
+}
+
+fn unsafe_item<'a>(
+    analysis: &'a Analysis,
+    id: ItemId<'a>,
+    item: &'a rustdoc_types::Item,
+) -> Option<UnsafeItem<'a>> {
+    let kind = unsafe_kind(analysis, &id, item)?;
+    let name = item.name.as_deref().unwrap_or("<unnamed>");
+    let label = match &item.inner {
+        rustdoc_types::ItemEnum::Impl(i) => {
+            let trait_ = i.trait_.as_ref().map(|t| t.path.as_str()).unwrap_or("Self");
+            format!("{} {trait_} for {}", kind.name(), type_label(&i.for_))
+        }
+        _ => format!("{} {name}", kind.name()),
+    };
+    let module = analysis
+        .containing_module(&id)
+        .and_then(|module| analysis.get_path(module, "module"))
+        .map(|path| path.join("::"))
+        .unwrap_or_else(|| id.crate_name().to_owned());
+    Some(UnsafeItem {
+        id,
+        item,
+        kind,
+        module,
+        label,
+    })
+}
+
+fn unsafe_kind(analysis: &Analysis, id: &ItemId, item: &rustdoc_types::Item) -> Option<UnsafeKind> {
+    use rustdoc_types::ItemEnum::*;
+    match &item.inner {
+        Function(f) => {
+            // Functions declared in extern blocks have a foreign ABI and no body. Calling them is
+            // unsafe unless they're declared `safe`, but either way they need review.
+            if !f.has_body && f.header.abi != rustdoc_types::Abi::Rust {
+                Some(UnsafeKind::ExternFunction)
+            } else {
+                f.header.is_unsafe.then_some(UnsafeKind::Function)
+            }
+        }
+        Trait(t) => t.is_unsafe.then_some(UnsafeKind::Trait),
+        Impl(i) => {
+            if i.is_synthetic {
+                return None;
+            }
+            // rustdoc doesn't always set is_unsafe, but an impl of an unsafe trait must be unsafe.
+            let trait_ = i.trait_.as_ref()?;
+            let trait_id = id.crate_sibling(&trait_.id);
+            let unsafe_trait = match analysis.get_item(trait_id.clone()) {
+                Some((
+                    _,
+                    Item::Item(rustdoc_types::Item {
+                        inner: Trait(t), ..
+                    }),
+                )) => t.is_unsafe,
+                _ => analysis
+                    .get_path(trait_id, &trait_.path)
+                    .is_some_and(|path| UNSAFE_AUTO_TRAITS.iter().any(|p| path == *p)),
+            };
+            (i.is_unsafe || unsafe_trait).then_some(UnsafeKind::Impl)
+        }
+        Static(s) if s.is_mutable => Some(UnsafeKind::StaticMut),
+        Static(s) if s.is_unsafe => Some(UnsafeKind::ExternStatic),
+        _ => None,
+    }
+}
+
+/// Print the unsafe items of each workspace crate, grouped by crate and module, with where
+/// they're defined. Returns how many there were.
+pub fn write_report(analysis: &Analysis, out: &mut impl Write) -> io::Result<usize> {
+    let mut crate_names = analysis
+        .crates
+        .keys()
+        .filter(|name| analysis.is_workspace_crate(name))
+        .collect::<Vec<_>>();
+    crate_names.sort();
+
+    let mut total = 0;
+    for crate_name in crate_names {
+        let items = unsafe_items(analysis, crate_name);
+        if items.is_empty() {
+            continue;
+        }
+        writeln!(out, "{crate_name}")?;
+        let mut module = None;
+        for item in &items {
+            if module != Some(&item.module) {
+                writeln!(out, "  {}", item.module)?;
+                module = Some(&item.module);
+            }
+            let span = item.item.span.as_ref().unwrap();
+            writeln!(
+                out,
+                "    {} ({}:{})",
+                item.label,
+                span.filename.display(),
+                span.begin.0
+            )?;
+        }
+        total += items.len();
+    }
+    writeln!(out, "{total} unsafe items")?;
+    Ok(total)
+}
@@ -0,0 +1,126 @@
+use crate::browser_rustdoc::get_source_for_item;
+use crate::browser_trait::Browser;
+use crate::diff::Change;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+/// Browses the differences between two versions of some crates' public APIs, arranged in a tree by
+/// their paths.
+pub struct DiffBrowser<'a> {
+    changes: Vec<Change<'a>>,
+    old_root: PathBuf,
+    new_root: PathBuf,
+}
+
+/// A path prefix, starting with the crate name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffId(Vec<String>);
+
+impl<'a> DiffBrowser<'a> {
+    /// `old_root` and `new_root` are where the source paths of each version are relative to.
+    pub fn new(changes: Vec<Change<'a>>, old_root: PathBuf, new_root: PathBuf) -> Self {
+        Self {
+            changes,
+            old_root,
+            new_root,
+        }
+    }
+}
+
+impl<'a> Browser for &'a DiffBrowser<'a> {
+    /// The change at a path, if there is one and it isn't just a parent of other changes.
+    type Item = Option<&'a Change<'a>>;
+    type ItemId = DiffId;
+
+    fn list_crates(&self) -> Vec<(String, DiffId)> {
+        let mut crates = self
+            .changes
+            .iter()
+            .map(|change| change.path[0].clone())
+            .collect::<Vec<_>>();
+        crates.dedup();
+        crates
+            .into_iter()
+            .map(|name| (name.clone(), DiffId(vec![name])))
+            .collect()
+    }
+
+    fn list_items(&self, parent_id: &DiffId) -> Vec<(String, (DiffId, Option<&'a Change<'a>>))> {
+        let prefix = &parent_id.0;
+        // Number of changes under each child path.
+        let mut children = BTreeMap::<&str, usize>::new();
+        for change in &self.changes {
+            if change.path.len() > prefix.len() && change.path.starts_with(prefix) {
+                *children.entry(&change.path[prefix.len()]).or_default() += 1;
+            }
+        }
+
+        children
+            .into_iter()
+            .map(|(name, count)| {
+                let mut path = prefix.clone();
+                path.push(name.to_owned());
+                let change = self.changes.iter().find(|change| change.path == path);
+                let label = match change {
+                    Some(change) => {
+                        let item = change.new.as_ref().or(change.old.as_ref()).unwrap();
+                        match item.kind_name() {
+                            Some(kind) => format!("{} {kind} {name}", change.kind.marker()),
+                            None => format!("{} {name}", change.kind.marker()),
+                        }
+                    }
+                    None => format!("{name} ({count})"),
+                };
+                (label, (DiffId(path), change))
+            })
+            .collect()
+    }
+
+    fn list_view_options(&self, _crate_id: &DiffId) -> Vec<(String, bool)> {
+        vec![]
+    }
+
+    fn set_view_option(&self, _crate_id: &DiffId, _option: &str, _enabled: bool) {}
+
+    fn get_info(&self, item: &Option<&'a Change<'a>>) -> String {
+        let mut txt = String::new();
+        let Some(change) = item else {
+            return txt;
+        };
+        writeln!(txt, "{:?} {}", change.kind, change.path.join("::")).unwrap();
+        if let Some(old) = &change.old {
+            writeln!(txt, "old: {}", old.signature).unwrap();
+        }
+        if let Some(new) = &change.new {
+            writeln!(txt, "new: {}", new.signature).unwrap();
+        }
+        if let Some(docs) = change
+            .new
+            .as_ref()
+            .or(change.old.as_ref())
+            .and_then(|item| item.item.docs.as_ref())
+        {
+            txt.push('\n');
+            txt += docs;
+        }
+        txt
+    }
+
+    fn get_debug_info(&self, item: &Option<&'a Change<'a>>) -> String {
+        format!("{item:#?}")
+    }
+
+    fn get_source(&self, item: &Option<&'a Change<'a>>) -> (String, Option<usize>) {
+        let Some(change) = item else {
+            return (String::new(), None);
+        };
+        let (item, root) = match (&change.new, &change.old) {
+            (Some(new), _) => (new, &self.new_root),
+            (None, Some(old)) => (old, &self.old_root),
+            (None, None) => return (String::new(), None),
+        };
+        let (txt, line) = get_source_for_item(item.item, root);
+        (txt, Some(line))
+    }
+}
@@ -1,23 +1,278 @@
-use crate::analysis::{self, Analysis, Item, ItemId};
-use crate::browser_trait::Browser;
+use crate::analysis::{self, Analysis, CrateId, Item, ItemId};
+use crate::audit::UnsafeIndex;
+use crate::browser_trait::{Browser, ImplAnswer, SearchResult};
+use crate::conversions::{self, ConversionIndex, Direction};
+use crate::coverage::{Coverage, CoverageIndex};
+use crate::diff;
+use crate::graph::{self, Graph};
+use crate::impl_query::{self, Answer};
+use crate::layout::TypeLayouts;
+use crate::search::DocIndex;
+use crate::substitute::{self, Substitution};
+use crate::type_search::{self, SignatureQuery};
+use std::collections::{HashMap, HashSet};
 use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+const PRIVATE_ITEMS_OPTION: &str = "private items";
+const HIDDEN_ITEMS_OPTION: &str = "#[doc(hidden)] items";
+const API_VIEW_OPTION: &str = "public API by import path";
+const RAW_IMPLS_OPTION: &str = "blanket and auto trait impls inline";
+
+/// Appended to the labels of deprecated items.
+const DEPRECATED_MARKER: &str = " ⚠ deprecated";
 
 pub struct RustdocBrowser {
     analysis: Analysis,
+    view_options: RwLock<HashMap<String, ViewOptions>>,
+    layouts: TypeLayouts,
+    output_dir: Option<PathBuf>,
+    doc_index: DocIndex,
+    conversion_index: ConversionIndex,
+    unsafe_index: UnsafeIndex,
+    coverage_index: CoverageIndex,
+}
+
+/// Per-crate settings for what gets shown.
+#[derive(Debug, Clone, Copy)]
+struct ViewOptions {
+    private_items: bool,
+    hidden_items: bool,
+    /// List modules' publicly reachable items, following re-exports, instead of what's declared
+    /// in them.
+    api_view: bool,
+    /// List blanket and auto trait impls along with the others, instead of in groups.
+    raw_impls: bool,
+}
+
+/// Identifies what to list the children of: an item, or one of the groups synthesized under it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BrowserId<'a> {
+    pub item: ItemId<'a>,
+    pub group: Option<Group>,
+    /// What the generic parameters of the item (or the type or impl it's in) stand for, when it
+    /// was reached through a type with generic arguments, like a field of type `Vec<Foo>`.
+    pub substitution: Option<Arc<Substitution<'a>>>,
+}
+
+impl<'a> From<ItemId<'a>> for BrowserId<'a> {
+    fn from(item: ItemId<'a>) -> Self {
+        Self {
+            item,
+            group: None,
+            substitution: None,
+        }
+    }
+}
+
+/// Groups of an item's children which are listed under a row of their own, instead of directly
+/// under the item.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Group {
+    /// Impls of auto traits (`Send`, `Sync`, etc.) that rustdoc determined apply to a type.
+    AutoTraitImpls,
+    /// Impls of traits for any type satisfying some bounds, which apply to a type.
+    BlanketImpls,
+    /// Methods available on a type through (possibly repeated) `Deref`.
+    DerefMethods,
+    /// Methods from all of a type's impls.
+    AllMethods,
+    /// Functions in any crate which return a type.
+    Constructors,
+    /// Types a type can be converted from or into.
+    Conversions,
+    /// Traits which a trait requires implementors to also implement.
+    Supertraits,
+    /// Traits which have a trait as a supertrait.
+    Subtraits,
+    /// An item's generic parameters and where clause.
+    Generics,
+    /// The bounds on one of an item's generic parameters, by index.
+    GenericParam(usize),
+    /// The bounds in one of the predicates in an item's where clause, by index.
+    WherePredicate(usize),
+    /// Macros defined anywhere in a crate.
+    Macros,
+    /// Other loaded crates which a crate refers to.
+    Dependencies,
+    /// Other loaded crates which refer to a crate.
+    Dependents,
+    /// Unsafe functions, traits, impls and statics anywhere in a workspace crate.
+    Unsafe,
+    /// How much of each module of a workspace crate is documented.
+    DocCoverage,
+    /// Which public items declared in a module are documented.
+    ModuleDocCoverage,
 }
 
 impl RustdocBrowser {
     pub fn new(analysis: Analysis) -> Self {
-        Self { analysis }
+        Self {
+            doc_index: DocIndex::build(&analysis),
+            conversion_index: ConversionIndex::build(&analysis),
+            unsafe_index: UnsafeIndex::build(&analysis),
+            coverage_index: CoverageIndex::build(&analysis),
+            analysis,
+            view_options: RwLock::new(HashMap::new()),
+            layouts: TypeLayouts::default(),
+            output_dir: None,
+        }
+    }
+
+    /// Show these type layouts in the info of the types they're for.
+    pub fn with_layouts(mut self, layouts: TypeLayouts) -> Self {
+        self.layouts = layouts;
+        self
+    }
+
+    /// Save files exported from the browser, like graphs, in this directory.
+    pub fn with_output_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.output_dir = Some(dir.into());
+        self
+    }
+
+    /// The memory layout of a concrete struct, enum, or union, if the compiler reported one.
+    fn type_layout(&self, item: &rustdoc_types::Item) -> Option<&crate::layout::TypeLayout> {
+        use rustdoc_types::{GenericParamDefKind, ItemEnum};
+        let generics = match &item.inner {
+            ItemEnum::Struct(s) => &s.generics,
+            ItemEnum::Enum(e) => &e.generics,
+            ItemEnum::Union(u) => &u.generics,
+            _ => return None,
+        };
+        if generics
+            .params
+            .iter()
+            .any(|p| !matches!(p.kind, GenericParamDefKind::Lifetime { .. }))
+        {
+            return None;
+        }
+        let id = self.analysis.find_item_id(item)?;
+        let path = self
+            .analysis
+            .get_path(id, item.name.as_deref().unwrap_or_default())?;
+        self.layouts.get(path)
+    }
+
+    fn view_options(&self, crate_name: &str) -> ViewOptions {
+        if let Some(opts) = self.view_options.read().unwrap().get(crate_name) {
+            return *opts;
+        }
+        // Show everything in workspace crates, but only the public API of dependencies.
+        let workspace = self.analysis.is_workspace_crate(crate_name);
+        ViewOptions {
+            private_items: workspace,
+            hidden_items: workspace,
+            api_view: false,
+            raw_impls: false,
+        }
+    }
+
+    /// Whether the item should be listed under the given parent, according to the view options of
+    /// the item's crate.
+    fn is_shown(
+        &self,
+        id: &ItemId,
+        item: &rustdoc_types::Item,
+        parent: Option<&rustdoc_types::Item>,
+    ) -> bool {
+        let opts = self.view_options(id.crate_name());
+        if !opts.hidden_items && analysis::is_doc_hidden(item) {
+            return false;
+        }
+        if !opts.private_items && !analysis::is_public(item, parent) {
+            return false;
+        }
+        true
     }
 
     fn item_label(&self, id: ItemId, item: &rustdoc_types::Item) -> String {
-        use rustdoc_types::ItemEnum::*;
         let name = item.name.as_deref().unwrap_or("<unnamed>");
+        self.named_item_label(id, item, name)
+    }
+
+    /// Like `item_label`, with generic parameters replaced by what they stand for, if known.
+    fn substituted_label(
+        &self,
+        id: ItemId,
+        item: &rustdoc_types::Item,
+        substitution: Option<&Arc<Substitution>>,
+    ) -> String {
+        match substitution {
+            Some(substitution) => self.item_label(id, &substitution.apply_item(item)),
+            None => self.item_label(id, item),
+        }
+    }
+
+    /// Carry a type's substitution over to one of its impls.
+    fn impl_substitution<'a>(
+        &'a self,
+        type_id: &ItemId<'a>,
+        substitution: Option<&Arc<Substitution<'a>>>,
+        impl_: &'a rustdoc_types::Impl,
+    ) -> Option<Arc<Substitution<'a>>> {
+        let substitution = substitution?;
+        let (_, Item::Item(ty)) = self.analysis.get_item(type_id.clone())? else {
+            return None;
+        };
+        substitution.for_impl(&substitute::type_generics(ty)?.params, impl_)
+    }
+
+    /// Like `item_label`, but with the item's name replaced (e.g. when it's renamed by a `use`).
+    fn named_item_label(&self, id: ItemId, item: &rustdoc_types::Item, name: &str) -> String {
+        let mut label = self.kind_label(id.clone(), item, name);
+        if item.deprecation.is_some() {
+            label.push_str(DEPRECATED_MARKER);
+        }
+        match self.visibility_label(id, &item.visibility) {
+            Some(vis) => format!("{vis} {label}"),
+            None => label,
+        }
+    }
+
+    /// Whether the visibility is restricted to the module the item is declared in.
+    fn is_private(&self, id: &ItemId, vis: &rustdoc_types::Visibility) -> bool {
+        // rustdoc represents private items as being restricted to their own module.
+        let rustdoc_types::Visibility::Restricted { parent, .. } = vis else {
+            return false;
+        };
+        self.analysis
+            .containing_module(id)
+            .is_some_and(|module| module == id.crate_sibling(parent))
+    }
+
+    fn visibility_label(&self, id: ItemId, vis: &rustdoc_types::Visibility) -> Option<String> {
+        use rustdoc_types::Visibility::*;
+        match vis {
+            Public => Some("pub".to_owned()),
+            Crate => Some("pub(crate)".to_owned()),
+            Restricted { path, .. } => {
+                if self.is_private(&id, vis) {
+                    None
+                } else {
+                    Some(format!("pub(in {path})"))
+                }
+            }
+            // Inherited from the parent (e.g. trait items and enum variants).
+            Default => None,
+        }
+    }
+
+    fn kind_label(&self, id: ItemId, item: &rustdoc_types::Item, name: &str) -> String {
+        use rustdoc_types::ItemEnum::*;
         let prefix = match &item.inner {
             Module(_) => "mod",
             ExternCrate { name, .. } => return format!("extern crate {name}"),
-            Use(u) => return format!("use {}", u.source) + if u.is_glob { "::*" } else { "" },
+            Use(u) => {
+                return if u.is_glob {
+                    format!("use {}::*", u.source)
+                } else if u.source.rsplit("::").next() != Some(&u.name) {
+                    format!("use {} as {}", u.source, u.name)
+                } else {
+                    format!("use {}", u.source)
+                };
+            }
             Union(_) => "union",
             Struct(_) => "struct",
             StructField(f) => return format!("{}: {}", name, type_label(f)),
@@ -57,7 +312,8 @@ impl RustdocBrowser {
                     if let Some(g) = &trait_.args {
                         trait_name.push_str(&generic_label(g));
                     }
-                    format!("impl {trait_name}")
+                    let negative = if i.is_negative { "!" } else { "" };
+                    format!("impl {negative}{trait_name}")
                 } else {
                     "impl Self".to_string()
                 };
@@ -66,8 +322,8 @@ impl RustdocBrowser {
             Constant { type_, const_: _ } => return format!("const {}: {}", name, type_label(type_)),
             Static(s) => return format!("static {}: {}", name, type_label(&s.type_)),
             ExternType => "extern type",
-            Macro(_) => "macro",
-            ProcMacro(_) => "proc macro",
+            Macro(_) => "macro_rules!",
+            ProcMacro(p) => return proc_macro_label(p, name),
             Primitive(_) => "",
             AssocConst { type_, value } => {
                 return if let Some(value) = value {
@@ -75,16 +331,1347 @@ impl RustdocBrowser {
                 } else {
                     format!("const {name}: {}", type_label(type_))
                 }
-            }
-            AssocType { type_, .. } => {
-                return if let Some(type_) = type_ {
-                    format!("type {name} = {}", type_label(type_))
+            }
+            AssocType { type_, .. } => {
+                return if let Some(type_) = type_ {
+                    format!("type {name} = {}", type_label(type_))
+                } else {
+                    format!("type {name}")
+                };
+            }
+        };
+        format!("{prefix} {name}")
+    }
+
+    /// For methods listed under a trait impl, a marker saying whether they're inherited from the
+    /// trait's default implementation or override it.
+    fn impl_method_marker<'a>(
+        &'a self,
+        impl_id: &ItemId<'a>,
+        impl_: &'a rustdoc_types::Impl,
+        id: &ItemId<'a>,
+        item: &rustdoc_types::Item,
+    ) -> Option<&'static str> {
+        if !matches!(item.inner, rustdoc_types::ItemEnum::Function(_)) {
+            return None;
+        }
+        let (trait_id, trait_) = self.analysis.impl_trait(impl_id, impl_)?;
+        if self.analysis.parent(id).as_ref() != Some(impl_id) {
+            return Some("(default)");
+        }
+        let overrides = trait_
+            .items
+            .iter()
+            .filter_map(|child| self.analysis.get_item(trait_id.crate_sibling(child)))
+            .any(|(_, child)| match child {
+                Item::Item(child) => {
+                    child.name == item.name
+                        && matches!(&child.inner, rustdoc_types::ItemEnum::Function(f) if f.has_body)
+                }
+                Item::Root => false,
+            });
+        overrides.then_some("(override)")
+    }
+
+    fn list_item_children<'a>(
+        &'a self,
+        parent_id: &ItemId<'a>,
+        substitution: Option<&Arc<Substitution<'a>>>,
+    ) -> Vec<(String, (BrowserId<'a>, Item<'a>))> {
+        // If true, skip showing this element's children and show the children of the first child
+        // instead. Basically, skip one level of nesting. Use when the item is redundant.
+        let mut use_first_child = false;
+
+        let mut synthetic_items: Vec<(String, (ItemId<'a>, Item<'a>))> = vec![];
+
+        let (resolved_parent_id, parent) = match self.analysis.get_item(parent_id.clone()) {
+            Some((id, Item::Item(parent))) => (id, Some(parent)),
+            _ => (parent_id.clone(), None),
+        };
+
+        if let Some(parent) = parent {
+            match &parent.inner {
+                rustdoc_types::ItemEnum::Variant(v)
+                    if self
+                        .single_element_tuple_variant(v, parent_id.clone())
+                        .is_some() =>
+                {
+                    // Single-element tuple variant. The only child is a StructField for field "0".
+                    // This adds nothing because the type name is already in the Variant's label.
+                    use_first_child = true;
+                }
+                rustdoc_types::ItemEnum::StructField(ty) => {
+                    // If the type has generic arguments, or is a generic parameter whose argument
+                    // is known, list its children with them substituted.
+                    if let Some((type_id, type_substitution)) =
+                        substitute::target(&self.analysis, parent_id, ty, substitution)
+                    {
+                        if substitution.is_some() || type_substitution.is_some() {
+                            return self.list_item_children(&type_id, type_substitution.as_ref());
+                        }
+                    }
+                    // StructField's only child is the type, which adds nothing, as the type name is
+                    // already in the StructField's label.
+                    use_first_child = true;
+                }
+                rustdoc_types::ItemEnum::Function(f) => {
+                    synthetic_items = f
+                        .sig
+                        .inputs
+                        .iter()
+                        // TODO: implement special cases for names of self:
+                        //  - self
+                        //  - &self
+                        //  - &mut self
+                        .map(|(name, ty)| (format!("{name}:"), ty))
+                        .chain(f.sig.output.as_ref().map(|ty| ("->".to_owned(), ty)))
+                        .map(|(name, ty)| {
+                            let mut ids = analysis::type_ids(ty)
+                                .into_iter()
+                                .map(|id| parent_id.crate_sibling(id))
+                                .collect::<Vec<_>>();
+                            if ids.is_empty() && substitution.is_some() {
+                                // It may be a generic parameter whose argument is known.
+                                ids.extend(
+                                    substitute::target(&self.analysis, parent_id, ty, substitution)
+                                        .map(|(id, _)| id),
+                                );
+                            }
+                            if ids.is_empty() {
+                                // Inject a fake ID so that the label at least shows up.
+                                ids.push(analysis::EMPTY_ITEM_ID.clone());
+                            }
+                            (name, ty, ids)
+                        })
+                        .flat_map(|(name, ty, ids)| {
+                            let use_suffix = ids.len() > 1;
+                            ids.into_iter().enumerate().map(move |(i, id)| {
+                                let tylabel = match substitution {
+                                    Some(substitution) => type_label(&substitution.apply(ty)),
+                                    None => type_label(ty),
+                                };
+                                let mut label = format!("{name} {tylabel}");
+                                if use_suffix {
+                                    label += &format!(" (#{i}");
+                                }
+                                (label, (id, Item::Item(parent)))
+                            })
+                        })
+                        .collect::<Vec<_>>();
+                }
+                _ => (),
+            }
+        }
+
+        // Children of a type get its substitution, translated to the impl's parameters for impls.
+        let child_substitution = |child: &Item<'a>| {
+            let substitution = substitution?;
+            match child {
+                Item::Item(rustdoc_types::Item {
+                    inner: rustdoc_types::ItemEnum::Impl(i),
+                    ..
+                }) => self.impl_substitution(&resolved_parent_id, Some(substitution), i),
+                _ => Some(substitution.clone()),
+            }
+        };
+
+        // The children of a `use` are those of what it imports, and are as visible as they are
+        // there.
+        let children_parent = parent.map(|p| {
+            self.analysis
+                .resolve_use(&resolved_parent_id, p)
+                .map_or(p, |(_, target)| target)
+        });
+
+        let mut items = match parent.map(|p| &p.inner) {
+            Some(rustdoc_types::ItemEnum::Module(m))
+                if self.view_options(resolved_parent_id.crate_name()).api_view =>
+            {
+                self.public_api_items(&resolved_parent_id, m)
+            }
+            _ => self
+                .analysis
+                .items(parent_id)
+                .filter_map(|(id, item)| {
+                    use rustdoc_types::ItemEnum::*;
+                    let inner = match item {
+                        Item::Root => return None,
+                        Item::Item(item) => item,
+                    };
+
+                    if !self.is_shown(&id, inner, children_parent) {
+                        return None;
+                    }
+
+                    // Glob imports don't bring private items into scope.
+                    if parent.is_some_and(|p| matches!(&p.inner, Use(u) if u.is_glob))
+                        && self.is_private(&id, &inner.visibility)
+                    {
+                        return None;
+                    }
+
+                    // Remove the clutter of blanket, and synthetic trait impls. They're listed in
+                    // groups instead, unless the raw listing is turned on.
+                    if let Impl(i) = &inner.inner {
+                        if group_of_impl(i).is_some()
+                            && !self.view_options(id.crate_name()).raw_impls
+                        {
+                            return None;
+                        }
+                    }
+
+                    let mut label = self.substituted_label(
+                        id.clone(),
+                        inner,
+                        child_substitution(&item).as_ref(),
+                    );
+                    if let Some(Impl(i)) = parent.map(|p| &p.inner) {
+                        if let Some(marker) =
+                            self.impl_method_marker(&resolved_parent_id, i, &id, inner)
+                        {
+                            label = format!("{label} {marker}");
+                        }
+                    }
+                    Some((label, (id, item)))
+                })
+                .collect::<Vec<_>>(),
+        };
+        sort_by_label(&mut items);
+
+        if use_first_child && !items.is_empty() {
+            assert_eq!(
+                items.len(),
+                1,
+                "use_first_child on non-singleton children list: {items:#?}"
+            );
+            return self.list_item_children(&items[0].1 .0, substitution);
+        }
+
+        let items = items.into_iter().map(|(label, (id, item))| {
+            let id = BrowserId {
+                substitution: child_substitution(&item),
+                ..id.into()
+            };
+            (label, (id, item))
+        });
+        // The synthetic items lead to other types, which the substitution isn't for.
+        let mut items = synthetic_items
+            .into_iter()
+            .map(|(label, (id, item))| (label, (id.into(), item)))
+            .chain(items)
+            .collect::<Vec<_>>();
+
+        // The groups of a type's impls and methods get its substitution too.
+        if !self.view_options(resolved_parent_id.crate_name()).raw_impls {
+            items.extend(self.impl_group_rows(&resolved_parent_id, substitution));
+        }
+        items.extend(self.deref_row(&resolved_parent_id, substitution));
+        items.extend(self.methods_row(&resolved_parent_id, substitution));
+        items.extend(self.constructors_row(&resolved_parent_id));
+        items.extend(self.conversions_row(&resolved_parent_id));
+        items.extend(self.generics_row(&resolved_parent_id, substitution));
+        items.extend(self.trait_rows(&resolved_parent_id));
+        items.extend(self.crate_rows(&resolved_parent_id));
+
+        items
+    }
+
+    /// Names of the traits whose impls for a type were generated by `#[derive]`.
+    fn derived_traits<'a>(&'a self, item: &'a rustdoc_types::Item) -> Vec<&'a str> {
+        let Some(id) = self.analysis.find_item_id(item) else {
+            return vec![];
+        };
+        self.type_impls(&id)
+            .into_iter()
+            .filter(|(_, impl_item, _)| {
+                impl_item
+                    .attrs
+                    .iter()
+                    .any(|attr| attr == "#[automatically_derived]")
+            })
+            .filter_map(|(_, _, i)| {
+                let name = i.trait_.as_ref()?.path.rsplit("::").next()?;
+                // Derived along with PartialEq, as a compiler implementation detail.
+                (name != "StructuralPartialEq").then_some(name)
+            })
+            .collect()
+    }
+
+    /// Look up a trait, possibly through a `use` of it.
+    fn resolve_trait<'a>(
+        &'a self,
+        id: &ItemId<'a>,
+    ) -> Option<(
+        ItemId<'a>,
+        &'a rustdoc_types::Item,
+        &'a rustdoc_types::Trait,
+    )> {
+        let (id, Item::Item(item)) = self.analysis.get_item(id.clone())? else {
+            return None;
+        };
+        let (id, item) = self.analysis.resolve_use(&id, item).unwrap_or((id, item));
+        match &item.inner {
+            rustdoc_types::ItemEnum::Trait(t) => Some((id, item, t)),
+            _ => None,
+        }
+    }
+
+    /// Rows under a trait leading to its supertraits and subtraits.
+    fn trait_rows<'a>(&'a self, trait_id: &ItemId<'a>) -> Vec<(String, (BrowserId<'a>, Item<'a>))> {
+        let Some((trait_id, item, t)) = self.resolve_trait(trait_id) else {
+            return vec![];
+        };
+        [
+            (
+                "supertraits",
+                Group::Supertraits,
+                analysis::supertrait_bounds(t).len(),
+            ),
+            (
+                "subtraits",
+                Group::Subtraits,
+                self.analysis.subtraits(&trait_id).len(),
+            ),
+        ]
+        .into_iter()
+        .filter(|(_, _, count)| *count != 0)
+        .map(|(label, group, count)| {
+            (
+                format!("{label} ({count})"),
+                (
+                    BrowserId {
+                        item: trait_id.clone(),
+                        group: Some(group),
+                        substitution: None,
+                    },
+                    Item::Item(item),
+                ),
+            )
+        })
+        .collect()
+    }
+
+    fn list_supertraits<'a>(
+        &'a self,
+        trait_id: &ItemId<'a>,
+    ) -> Vec<(String, (BrowserId<'a>, Item<'a>))> {
+        let Some((trait_id, item, t)) = self.resolve_trait(trait_id) else {
+            return vec![];
+        };
+        self.bound_rows(&trait_id, item, analysis::supertrait_bounds(t))
+    }
+
+    /// Rows leading to the traits in some bounds. Traits from crates that weren't loaded still get
+    /// listed, but lead nowhere, and show the item with the bounds.
+    fn bound_rows<'a>(
+        &'a self,
+        owner_id: &ItemId<'a>,
+        owner: &'a rustdoc_types::Item,
+        bounds: impl IntoIterator<Item = &'a rustdoc_types::GenericBound>,
+    ) -> Vec<(String, (BrowserId<'a>, Item<'a>))> {
+        bounds
+            .into_iter()
+            .filter_map(|bound| {
+                let rustdoc_types::GenericBound::TraitBound { trait_, .. } = bound else {
+                    return None;
+                };
+                let label = format!("trait {}", bound_label(bound));
+                let trait_id = owner_id.crate_sibling(&trait_.id);
+                let row = self
+                    .analysis
+                    .get_item(trait_id.clone())
+                    .unwrap_or((trait_id, Item::Item(owner)));
+                Some((label, (row.0.into(), row.1)))
+            })
+            .collect()
+    }
+
+    /// Look up an item with generics, possibly through a `use` of it.
+    fn resolve_generics<'a>(
+        &'a self,
+        id: &ItemId<'a>,
+    ) -> Option<(
+        ItemId<'a>,
+        &'a rustdoc_types::Item,
+        &'a rustdoc_types::Generics,
+    )> {
+        let (id, Item::Item(item)) = self.analysis.get_item(id.clone())? else {
+            return None;
+        };
+        let (id, item) = self.analysis.resolve_use(&id, item).unwrap_or((id, item));
+        use rustdoc_types::ItemEnum::*;
+        let generics = match &item.inner {
+            Struct(s) => &s.generics,
+            Enum(e) => &e.generics,
+            Union(u) => &u.generics,
+            Trait(t) => &t.generics,
+            TraitAlias(t) => &t.generics,
+            Impl(i) => &i.generics,
+            Function(f) => &f.generics,
+            TypeAlias(t) => &t.generics,
+            _ => return None,
+        };
+        Some((id, item, generics))
+    }
+
+    /// An item's generic parameters, leaving out ones rustdoc made up for `impl Trait` arguments.
+    fn shown_generic_params(
+        generics: &rustdoc_types::Generics,
+    ) -> impl Iterator<Item = (usize, &rustdoc_types::GenericParamDef)> {
+        generics.params.iter().enumerate().filter(|(_, p)| {
+            !matches!(
+                p.kind,
+                rustdoc_types::GenericParamDefKind::Type {
+                    is_synthetic: true,
+                    ..
+                }
+            )
+        })
+    }
+
+    fn generics_row<'a>(
+        &'a self,
+        id: &ItemId<'a>,
+        substitution: Option<&Arc<Substitution<'a>>>,
+    ) -> Option<(String, (BrowserId<'a>, Item<'a>))> {
+        let (id, item, generics) = self.resolve_generics(id)?;
+        let count = Self::shown_generic_params(generics).count() + generics.where_predicates.len();
+        (count != 0).then(|| {
+            (
+                format!("generics ({count})"),
+                (
+                    BrowserId {
+                        item: id,
+                        group: Some(Group::Generics),
+                        substitution: substitution.cloned(),
+                    },
+                    Item::Item(item),
+                ),
+            )
+        })
+    }
+
+    /// An item's generic parameters, with what they stand for if that's known, followed by its
+    /// where clause's predicates.
+    fn list_generics<'a>(
+        &'a self,
+        id: &ItemId<'a>,
+        substitution: Option<&Arc<Substitution<'a>>>,
+    ) -> Vec<(String, (BrowserId<'a>, Item<'a>))> {
+        let Some((id, item, generics)) = self.resolve_generics(id) else {
+            return vec![];
+        };
+        let row = |label, group| {
+            (
+                label,
+                (
+                    BrowserId {
+                        item: id.clone(),
+                        group: Some(group),
+                        substitution: None,
+                    },
+                    Item::Item(item),
+                ),
+            )
+        };
+        let param_label = |p: &rustdoc_types::GenericParamDef| {
+            let mut label = generic_param_label(p);
+            let param = rustdoc_types::Type::Generic(p.name.clone());
+            if let Some(ty) = substitution
+                .map(|substitution| substitution.apply(&param))
+                .filter(|ty| *ty != param)
+            {
+                write!(label, " (= {})", type_label(&ty)).unwrap();
+            }
+            label
+        };
+        Self::shown_generic_params(generics)
+            .map(|(i, p)| row(param_label(p), Group::GenericParam(i)))
+            .chain(
+                generics
+                    .where_predicates
+                    .iter()
+                    .enumerate()
+                    .map(|(i, pred)| {
+                        row(
+                            format!("where {}", where_predicate_label(pred)),
+                            Group::WherePredicate(i),
+                        )
+                    }),
+            )
+            .collect()
+    }
+
+    fn list_generic_bounds<'a>(
+        &'a self,
+        id: &ItemId<'a>,
+        group: Group,
+    ) -> Vec<(String, (BrowserId<'a>, Item<'a>))> {
+        let Some((id, item, generics)) = self.resolve_generics(id) else {
+            return vec![];
+        };
+        let bounds: &[rustdoc_types::GenericBound] = match group {
+            Group::GenericParam(i) => match generics.params.get(i).map(|p| &p.kind) {
+                Some(rustdoc_types::GenericParamDefKind::Type { bounds, .. }) => bounds,
+                _ => &[],
+            },
+            Group::WherePredicate(i) => match generics.where_predicates.get(i) {
+                Some(rustdoc_types::WherePredicate::BoundPredicate { bounds, .. }) => bounds,
+                _ => &[],
+            },
+            _ => &[],
+        };
+        self.bound_rows(&id, item, bounds)
+    }
+
+    fn list_subtraits<'a>(
+        &'a self,
+        trait_id: &ItemId<'a>,
+    ) -> Vec<(String, (BrowserId<'a>, Item<'a>))> {
+        let Some((trait_id, _, _)) = self.resolve_trait(trait_id) else {
+            return vec![];
+        };
+        let mut rows = self
+            .analysis
+            .subtraits(&trait_id)
+            .into_iter()
+            .filter_map(|id| {
+                let path = self.analysis.get_path(id.clone(), "subtrait")?;
+                let (id, item) = self.analysis.get_item(id)?;
+                Some((format!("trait {}", path.join("::")), (id.into(), item)))
+            })
+            .collect::<Vec<_>>();
+        sort_by_label(&mut rows);
+        rows
+    }
+
+    /// Rows under a crate's root leading to all its macros and (for workspace crates) unsafe code and
+    /// documentation coverage, and to the loaded crates it depends on and which depend on it.
+    fn crate_rows<'a>(&'a self, id: &ItemId<'a>) -> Vec<(String, (BrowserId<'a>, Item<'a>))> {
+        let Some((id, Item::Item(root))) = self.analysis.get_item(id.clone()) else {
+            return vec![];
+        };
+        if !matches!(&root.inner, rustdoc_types::ItemEnum::Module(m) if m.is_crate) {
+            return vec![];
+        }
+        let name = id.crate_name();
+        let workspace = self.analysis.is_workspace_crate(name);
+        let counted =
+            |label: &str, count: usize| (count != 0).then(|| format!("{label} ({count})"));
+        [
+            (
+                Group::Macros,
+                counted("macros", self.analysis.macros(name).len()),
+            ),
+            (
+                Group::Unsafe,
+                workspace
+                    .then(|| counted("unsafe", self.unsafe_index.count(name)))
+                    .flatten(),
+            ),
+            (
+                Group::DocCoverage,
+                workspace.then(|| {
+                    let percent = self.coverage_index.percent(name);
+                    format!("doc coverage ({}%)", percent.floor())
+                }),
+            ),
+            (
+                Group::Dependencies,
+                counted("dependencies", self.analysis.crate_dependencies(name).len()),
+            ),
+            (
+                Group::Dependents,
+                counted("dependents", self.analysis.crate_dependents(name).len()),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(group, label)| {
+            Some((
+                label?,
+                (
+                    BrowserId {
+                        item: id.clone(),
+                        group: Some(group),
+                        substitution: None,
+                    },
+                    Item::Item(root),
+                ),
+            ))
+        })
+        .collect()
+    }
+
+    fn list_macros<'a>(
+        &'a self,
+        crate_id: &ItemId<'a>,
+    ) -> Vec<(String, (BrowserId<'a>, Item<'a>))> {
+        let mut rows = self
+            .analysis
+            .macros(crate_id.crate_name())
+            .into_iter()
+            .filter_map(|id| {
+                let (id, item) = self.analysis.get_item(id)?;
+                let Item::Item(macro_) = item else {
+                    return None;
+                };
+                Some((self.item_label(id.clone(), macro_), (id.into(), item)))
+            })
+            .collect::<Vec<_>>();
+        sort_by_label(&mut rows);
+        rows
+    }
+
+    /// Unsafe items in a crate, in order of the modules they're in.
+    fn list_unsafe<'a>(
+        &'a self,
+        crate_id: &ItemId<'a>,
+    ) -> Vec<(String, (BrowserId<'a>, Item<'a>))> {
+        self.unsafe_index
+            .unsafe_items(&self.analysis, crate_id.crate_name())
+            .into_iter()
+            .map(|unsafe_item| {
+                (
+                    format!("{} (in {})", unsafe_item.label, unsafe_item.module),
+                    (unsafe_item.id.into(), Item::Item(unsafe_item.item)),
+                )
+            })
+            .collect()
+    }
+
+    fn list_doc_coverage<'a>(
+        &'a self,
+        crate_id: &ItemId<'a>,
+    ) -> Vec<(String, (BrowserId<'a>, Item<'a>))> {
+        self.coverage_index
+            .crate_coverage(&self.analysis, crate_id.crate_name())
+            .modules
+            .into_iter()
+            .filter_map(|module| {
+                let (_, item) = self.analysis.get_item(module.id.clone())?;
+                let label = format!(
+                    "{} {}/{} ({}%)",
+                    module.path,
+                    module.documented(),
+                    module.total(),
+                    module.percent().floor()
+                );
+                let id = BrowserId {
+                    item: module.id,
+                    group: Some(Group::ModuleDocCoverage),
+                    substitution: None,
+                };
+                Some((label, (id, item)))
+            })
+            .collect()
+    }
+
+    /// A module's public items, with the undocumented ones first and marked.
+    fn list_module_doc_coverage<'a>(
+        &'a self,
+        module_id: &ItemId<'a>,
+    ) -> Vec<(String, (BrowserId<'a>, Item<'a>))> {
+        let Some(module) = self
+            .coverage_index
+            .module_coverage(&self.analysis, module_id)
+        else {
+            return vec![];
+        };
+        let mut items = module.items;
+        items.sort_by_key(|item| item.documented);
+        items
+            .into_iter()
+            .map(|item| {
+                let mark = if item.documented { '✓' } else { '✗' };
+                (
+                    format!("{mark} {}", item.label),
+                    (item.id.into(), Item::Item(item.item)),
+                )
+            })
+            .collect()
+    }
+
+    fn list_crate_deps<'a>(
+        &'a self,
+        crate_id: &ItemId<'a>,
+        group: Group,
+    ) -> Vec<(String, (BrowserId<'a>, Item<'a>))> {
+        let names = match group {
+            Group::Dependencies => self.analysis.crate_dependencies(crate_id.crate_name()),
+            _ => self.analysis.crate_dependents(crate_id.crate_name()),
+        };
+        names
+            .into_iter()
+            .filter_map(|name| {
+                let id = ItemId::crate_root(CrateId { name });
+                let (_, item) = self.analysis.get_item(id.clone())?;
+                Some((crate_label(&id), (id.into(), item)))
+            })
+            .collect()
+    }
+
+    /// Look up a struct, enum or union, possibly through a `use` of it.
+    fn resolve_type<'a>(
+        &'a self,
+        id: &ItemId<'a>,
+    ) -> Option<(ItemId<'a>, &'a rustdoc_types::Item)> {
+        let (id, Item::Item(item)) = self.analysis.get_item(id.clone())? else {
+            return None;
+        };
+        let (id, item) = self.analysis.resolve_use(&id, item).unwrap_or((id, item));
+        use rustdoc_types::ItemEnum::*;
+        matches!(item.inner, Struct(_) | Enum(_) | Union(_) | Primitive(_)).then_some((id, item))
+    }
+
+    /// All the impls of a type, including ones rustdoc synthesized.
+    fn type_impls<'a>(
+        &'a self,
+        type_id: &ItemId<'a>,
+    ) -> Vec<(ItemId<'a>, &'a rustdoc_types::Item, &'a rustdoc_types::Impl)> {
+        use rustdoc_types::ItemEnum::*;
+        let Some((type_id, Item::Item(ty))) = self.analysis.get_item(type_id.clone()) else {
+            return vec![];
+        };
+        let impls = match &ty.inner {
+            Struct(s) => &s.impls,
+            Enum(e) => &e.impls,
+            Union(u) => &u.impls,
+            Primitive(p) => &p.impls,
+            _ => return vec![],
+        };
+        impls
+            .iter()
+            .filter_map(
+                |id| match self.analysis.get_item(type_id.crate_sibling(id))? {
+                    (id, Item::Item(item @ rustdoc_types::Item { inner: Impl(i), .. })) => {
+                        Some((id, item, i))
+                    }
+                    _ => None,
+                },
+            )
+            .collect()
+    }
+
+    /// The methods listed under an impl, including ones inherited from a trait.
+    fn impl_methods<'a>(
+        &'a self,
+        impl_id: &ItemId<'a>,
+        impl_item: &'a rustdoc_types::Item,
+    ) -> Vec<(ItemId<'a>, &'a rustdoc_types::Item)> {
+        self.analysis
+            .items(impl_id)
+            .filter_map(|(id, item)| match item {
+                Item::Item(method)
+                    if matches!(method.inner, rustdoc_types::ItemEnum::Function(_))
+                        && self.is_shown(&id, method, Some(impl_item)) =>
+                {
+                    Some((id, method))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Whether the impl is of the trait with the given canonical path.
+    fn is_impl_of(&self, impl_id: &ItemId, impl_: &rustdoc_types::Impl, path: &[&str]) -> bool {
+        let Some(trait_) = &impl_.trait_ else {
+            return false;
+        };
+        match self
+            .analysis
+            .get_path(impl_id.crate_sibling(&trait_.id), &trait_.path)
+        {
+            Some(trait_path) => trait_path == path,
+            // Only the name is known.
+            None => path.last() == Some(&trait_.path.as_str()),
+        }
+    }
+
+    /// If the type implements `Deref`, get its `Target` type, and the items that document it: the
+    /// struct, enum, or union it is, if it can be looked up, or for a primitive type like `str`,
+    /// the items for it in each loaded crate.
+    fn deref_target<'a>(
+        &'a self,
+        type_id: &ItemId<'a>,
+    ) -> Option<(
+        &'a rustdoc_types::Type,
+        Vec<ItemId<'a>>,
+        (ItemId<'a>, &'a rustdoc_types::Impl),
+    )> {
+        let (impl_id, impl_) = self
+            .type_impls(type_id)
+            .into_iter()
+            .find_map(|(id, _, i)| {
+                self.is_impl_of(&id, i, &["core", "ops", "deref", "Deref"])
+                    .then_some((id, i))
+            })?;
+        let target = impl_.items.iter().find_map(|id| {
+            match self.analysis.get_item(impl_id.crate_sibling(id))? {
+                (
+                    _,
+                    Item::Item(rustdoc_types::Item {
+                        name: Some(name),
+                        inner:
+                            rustdoc_types::ItemEnum::AssocType {
+                                type_: Some(ty), ..
+                            },
+                        ..
+                    }),
+                ) if name == "Target" => Some(ty),
+                _ => None,
+            }
+        })?;
+        let target_items = match target {
+            rustdoc_types::Type::ResolvedPath(p) => self
+                .resolve_type(&impl_id.crate_sibling(&p.id))
+                .map(|(id, _)| id)
+                .into_iter()
+                .collect(),
+            _ => analysis::primitive_name(target)
+                .map(|name| self.analysis.primitives(name))
+                .unwrap_or_default(),
+        };
+        Some((target, target_items, (impl_id, impl_)))
+    }
+
+    /// A row for the methods a type gets by dereferencing it, if it implements `Deref` and there
+    /// are any.
+    fn deref_row<'a>(
+        &'a self,
+        type_id: &ItemId<'a>,
+        substitution: Option<&Arc<Substitution<'a>>>,
+    ) -> Option<(String, (BrowserId<'a>, Item<'a>))> {
+        let (type_id, ty) = self.resolve_type(type_id)?;
+        let (target, _, (_, impl_)) = self.deref_target(&type_id)?;
+        if !self.has_deref_methods(&type_id) {
+            return None;
+        }
+        let target = match self.impl_substitution(&type_id, substitution, impl_) {
+            Some(substitution) => substitution.apply(target),
+            None => target.clone(),
+        };
+        Some((
+            format!("methods via Deref → {}", type_label(&target)),
+            (
+                BrowserId {
+                    item: type_id,
+                    group: Some(Group::DerefMethods),
+                    substitution: substitution.cloned(),
+                },
+                Item::Item(ty),
+            ),
+        ))
+    }
+
+    /// Whether any type in a type's chain of `Deref` targets has inherent methods, stopping at the
+    /// first one found.
+    fn has_deref_methods(&self, type_id: &ItemId) -> bool {
+        let mut visited = vec![type_id.clone()];
+        let mut type_id = type_id.clone();
+        while let Some((target, target_ids, _)) = self.deref_target(&type_id) {
+            if target_ids.is_empty() || target_ids.iter().any(|id| visited.contains(id)) {
+                break;
+            }
+            let found = target_ids.iter().any(|target_id| {
+                self.type_impls(target_id)
+                    .into_iter()
+                    .filter(|(_, _, i)| i.trait_.is_none())
+                    .any(|(impl_id, impl_item, _)| {
+                        !self.impl_methods(&impl_id, impl_item).is_empty()
+                    })
+            });
+            if found {
+                return true;
+            }
+            if !matches!(target, rustdoc_types::Type::ResolvedPath(_)) {
+                break;
+            }
+            visited.push(target_ids[0].clone());
+            type_id = target_ids[0].clone();
+        }
+        false
+    }
+
+    /// The inherent methods of each type in a type's chain of `Deref` targets, marked with how
+    /// many dereferences it takes to get to them.
+    fn list_deref_methods<'a>(
+        &'a self,
+        type_id: &ItemId<'a>,
+        substitution: Option<&Arc<Substitution<'a>>>,
+    ) -> Vec<(String, (BrowserId<'a>, Item<'a>))> {
+        let mut rows = vec![];
+        let mut visited = vec![type_id.clone()];
+        let mut type_id = type_id.clone();
+        let mut substitution = substitution.cloned();
+        while let Some((target, target_ids, (impl_id, impl_))) = self.deref_target(&type_id) {
+            if target_ids.is_empty() || target_ids.iter().any(|id| visited.contains(id)) {
+                break;
+            }
+            let level = visited.len();
+            // The target can use the impl's generic parameters, like `Vec<T>`'s `[T]`.
+            let deref_substitution = self.impl_substitution(&type_id, substitution.as_ref(), impl_);
+            let target_label = match &deref_substitution {
+                Some(substitution) => type_label(&substitution.apply(target)),
+                None => type_label(target),
+            };
+            let target_substitution = substitute::target(
+                &self.analysis,
+                &impl_id,
+                target,
+                deref_substitution.as_ref(),
+            )
+            .and_then(|(_, substitution)| substitution);
+            let mut methods = target_ids
+                .iter()
+                .flat_map(|target_id| {
+                    self.type_impls(target_id)
+                        .into_iter()
+                        .map(move |(impl_id, impl_item, i)| (target_id, impl_id, impl_item, i))
+                })
+                .filter(|(_, _, _, i)| i.trait_.is_none())
+                .flat_map(|(target_id, impl_id, impl_item, i)| {
+                    let substitution =
+                        self.impl_substitution(target_id, target_substitution.as_ref(), i);
+                    self.impl_methods(&impl_id, impl_item)
+                        .into_iter()
+                        .map(move |(id, method)| (id, method, substitution.clone()))
+                })
+                .map(|(id, method, substitution)| {
+                    let label = self.substituted_label(id.clone(), method, substitution.as_ref());
+                    let id = BrowserId {
+                        substitution,
+                        ..id.into()
+                    };
+                    (label, (id, Item::Item(method)))
+                })
+                .collect::<Vec<_>>();
+            sort_by_label(&mut methods);
+            // A primitive type's impls can be listed in more than one crate's documentation of it.
+            methods.dedup_by(|(a, _), (b, _)| a == b);
+            rows.extend(
+                methods.into_iter().map(|(label, row)| {
+                    (format!("{label} (deref #{level} → {target_label})"), row)
+                }),
+            );
+            // Primitive types don't dereference to anything further.
+            if !matches!(target, rustdoc_types::Type::ResolvedPath(_)) {
+                break;
+            }
+            visited.push(target_ids[0].clone());
+            type_id = target_ids[0].clone();
+            substitution = target_substitution;
+        }
+        rows
+    }
+
+    /// A row for all the methods callable on a type, if it has any.
+    fn methods_row<'a>(
+        &'a self,
+        type_id: &ItemId<'a>,
+        substitution: Option<&Arc<Substitution<'a>>>,
+    ) -> Option<(String, (BrowserId<'a>, Item<'a>))> {
+        let (type_id, ty) = self.resolve_type(type_id)?;
+        let count = self.all_methods(&type_id).len();
+        (count != 0).then(|| {
+            (
+                format!("methods ({count})"),
+                (
+                    BrowserId {
+                        item: type_id,
+                        group: Some(Group::AllMethods),
+                        substitution: substitution.cloned(),
+                    },
+                    Item::Item(ty),
+                ),
+            )
+        })
+    }
+
+    /// The methods from every impl of a type, with how each takes `self`, and the impl it's from.
+    /// Associated functions which don't take `self` are left out, as are trait methods which an
+    /// inherent method with the same name and receiver shadows.
+    fn all_methods<'a>(
+        &'a self,
+        type_id: &ItemId<'a>,
+    ) -> Vec<(
+        ItemId<'a>,
+        &'a rustdoc_types::Item,
+        String,
+        (ItemId<'a>, &'a rustdoc_types::Item),
+    )> {
+        let mut methods = vec![];
+        let mut inherent = HashSet::new();
+        for (impl_id, impl_item, i) in self.type_impls(type_id) {
+            for (id, method) in self.impl_methods(&impl_id, impl_item) {
+                let rustdoc_types::ItemEnum::Function(f) = &method.inner else {
+                    unreachable!("impl_methods only returns functions");
+                };
+                let Some(receiver) = receiver_label(f) else {
+                    continue;
+                };
+                if i.trait_.is_none() {
+                    inherent.insert((method.name.as_deref(), receiver.clone()));
+                }
+                methods.push((id, method, receiver, (impl_id.clone(), impl_item)));
+            }
+        }
+        methods.retain(|(_, method, receiver, (_, impl_item))| {
+            matches!(&impl_item.inner, rustdoc_types::ItemEnum::Impl(i) if i.trait_.is_none())
+                || !inherent.contains(&(method.name.as_deref(), receiver.clone()))
+        });
+        methods
+    }
+
+    /// Every method of a type, labeled with its receiver and the impl it's from.
+    fn list_all_methods<'a>(
+        &'a self,
+        type_id: &ItemId<'a>,
+        substitution: Option<&Arc<Substitution<'a>>>,
+    ) -> Vec<(String, (BrowserId<'a>, Item<'a>))> {
+        let mut methods = self
+            .all_methods(type_id)
+            .into_iter()
+            .map(|(id, method, receiver, (impl_id, impl_item))| {
+                let substitution = match &impl_item.inner {
+                    rustdoc_types::ItemEnum::Impl(i) => {
+                        self.impl_substitution(type_id, substitution, i)
+                    }
+                    _ => None,
+                };
+                let label = format!(
+                    "{} ({receiver}) [{}]",
+                    self.substituted_label(id.clone(), method, substitution.as_ref()),
+                    self.substituted_label(impl_id, impl_item, substitution.as_ref()),
+                );
+                let id = BrowserId {
+                    substitution,
+                    ..id.into()
+                };
+                (label, (id, Item::Item(method)))
+            })
+            .collect::<Vec<_>>();
+        // Not sort_by_label, which would put ones with "self: " in the label first.
+        methods.sort_by(|(a, _), (b, _)| strip_visibility(a).cmp(strip_visibility(b)));
+        methods.dedup_by(|(a, _), (b, _)| a == b);
+        methods
+    }
+
+    /// A row for the functions which return a type, if there are any.
+    fn constructors_row<'a>(
+        &'a self,
+        type_id: &ItemId<'a>,
+    ) -> Option<(String, (BrowserId<'a>, Item<'a>))> {
+        let (type_id, ty) = self.resolve_type(type_id)?;
+        let count = self.analysis.constructors(&type_id).len();
+        (count != 0).then(|| {
+            (
+                format!("constructors ({count})"),
+                (
+                    BrowserId {
+                        item: type_id,
+                        group: Some(Group::Constructors),
+                        substitution: None,
+                    },
+                    Item::Item(ty),
+                ),
+            )
+        })
+    }
+
+    /// Functions from any crate which return a type, labeled with their full path and signature,
+    /// and the trait impl they're from, if any.
+    fn list_constructors<'a>(
+        &'a self,
+        type_id: &ItemId<'a>,
+    ) -> Vec<(String, (BrowserId<'a>, Item<'a>))> {
+        let mut rows = self
+            .analysis
+            .constructors(type_id)
+            .into_iter()
+            .filter_map(|id| {
+                let (id, Item::Item(item)) = self.analysis.get_item(id)? else {
+                    return None;
+                };
+                let rustdoc_types::ItemEnum::Function(f) = &item.inner else {
+                    return None;
+                };
+                let mut label = diff::fn_signature(&self.analysis.full_name(&id, item), f);
+                let parent = self
+                    .analysis
+                    .parent(&id)
+                    .and_then(|parent| self.analysis.get_item(parent));
+                if let Some((impl_id, Item::Item(impl_item))) = parent {
+                    if matches!(&impl_item.inner, rustdoc_types::ItemEnum::Impl(i) if i.trait_.is_some())
+                    {
+                        write!(label, " [{}]", self.item_label(impl_id, impl_item)).unwrap();
+                    }
+                }
+                Some((label, (id.into(), Item::Item(item))))
+            })
+            .collect::<Vec<_>>();
+        // Not sort_by_label, which would put ones with ": " in the label first.
+        rows.sort_by(|(a, _), (b, _)| a.cmp(b));
+        rows
+    }
+
+    /// A row for the conversion trait impls between a type and others, if there are any.
+    fn conversions_row<'a>(
+        &'a self,
+        type_id: &ItemId<'a>,
+    ) -> Option<(String, (BrowserId<'a>, Item<'a>))> {
+        let (type_id, ty) = self.resolve_type(type_id)?;
+        let count = self
+            .conversion_index
+            .conversions(&self.analysis, &type_id)
+            .len();
+        (count != 0).then(|| {
+            (
+                format!("conversions ({count})"),
+                (
+                    BrowserId {
+                        item: type_id,
+                        group: Some(Group::Conversions),
+                        substitution: None,
+                    },
+                    Item::Item(ty),
+                ),
+            )
+        })
+    }
+
+    /// The types a type converts from, then the ones it converts into, labeled with the trait
+    /// that does it. Each leads to the other type, so the conversions can be followed from type to
+    /// type, or to the impl if the other type can't be browsed.
+    fn list_conversions<'a>(
+        &'a self,
+        type_id: &ItemId<'a>,
+    ) -> Vec<(String, (BrowserId<'a>, Item<'a>))> {
+        let mut rows = self
+            .conversion_index
+            .conversions(&self.analysis, type_id)
+            .into_iter()
+            .filter_map(|conversion| {
+                let direction = match conversion.direction {
+                    Direction::From => "from",
+                    Direction::Into => "into",
+                };
+                let other = conversion
+                    .other
+                    .map_or_else(|| "str".to_owned(), type_label);
+                let borrow = if conversion.is_borrow() { "&" } else { "" };
+                let label = format!("{direction} {borrow}{other} via {}", conversion.trait_name);
+                let target = match conversions::other_item(&self.analysis, &conversion) {
+                    Some((id, item)) => (id.into(), Item::Item(item)),
+                    None => {
+                        let (id, item) = self.analysis.get_item(conversion.impl_id)?;
+                        (id.into(), item)
+                    }
+                };
+                Some((label, target))
+            })
+            .collect::<Vec<_>>();
+        // Not sort_by_label, so all the "from" rows come before the "into" rows.
+        rows.sort_by(|(a, _), (b, _)| a.cmp(b));
+        rows
+    }
+
+    /// Look up a type and a trait by path and find the impls of one for the other, each labeled
+    /// with what kind of impl it is and what it says, including any bounds.
+    fn impl_answer<'a>(
+        &'a self,
+        type_path: &str,
+        trait_path: &str,
+    ) -> anyhow::Result<ImplAnswer<BrowserId<'a>>> {
+        let type_id = impl_query::resolve(&self.analysis, type_path, false)?;
+        let trait_id = impl_query::resolve(&self.analysis, trait_path, true)?;
+        let impls = impl_query::find_impls(&self.analysis, &type_id, &trait_id);
+        let full_path = |id: ItemId, fallback: &str| {
+            self.analysis
+                .get_path(id, fallback)
+                .map_or_else(|| fallback.to_owned(), |path| path.join("::"))
+        };
+        let summary = Answer::from_impls(&impls).describe(
+            &full_path(type_id, type_path),
+            &full_path(trait_id, trait_path),
+        );
+        let impls = impls
+            .into_iter()
+            .map(|m| {
+                let label = self.item_label(m.id.clone(), m.item);
+                let for_ = m.impl_.blanket_impl.as_ref().unwrap_or(&m.impl_.for_);
+                SearchResult {
+                    detail: format!(
+                        "impl{}{} for {}{}",
+                        generics_label(&m.impl_.generics),
+                        label.strip_prefix("impl").unwrap_or(&label),
+                        type_label(for_),
+                        where_label(&m.impl_.generics),
+                    ),
+                    label: m.kind.name().to_owned(),
+                    id: m.id.into(),
+                    highlight: None,
+                }
+            })
+            .collect();
+        Ok(ImplAnswer { summary, impls })
+    }
+
+    /// The impls of a type which belong in the given group, leaving out ones which are hidden, or
+    /// are of a trait which is.
+    fn group_impls<'a>(
+        &'a self,
+        type_id: &ItemId<'a>,
+        group: Group,
+    ) -> Vec<(ItemId<'a>, Item<'a>)> {
+        let ty = match self.analysis.get_item(type_id.clone()) {
+            Some((_, Item::Item(ty))) => Some(ty),
+            _ => None,
+        };
+        self.type_impls(type_id)
+            .into_iter()
+            .filter(|(_, _, i)| group_of_impl(i) == Some(group))
+            .filter(|(id, item, i)| {
+                let trait_shown = || match i.trait_.as_ref() {
+                    Some(t) => match self.analysis.get_item(id.crate_sibling(&t.id)) {
+                        Some((trait_id, Item::Item(trait_))) => {
+                            self.is_shown(&trait_id, trait_, None)
+                        }
+                        _ => true,
+                    },
+                    None => true,
+                };
+                self.is_shown(id, item, ty) && trait_shown()
+            })
+            .map(|(id, item, _)| (id, Item::Item(item)))
+            .collect()
+    }
+
+    /// Rows for the groups of impls listed under a type: a summary of which auto traits it
+    /// implements, and its blanket impls.
+    fn impl_group_rows<'a>(
+        &'a self,
+        type_id: &ItemId<'a>,
+        substitution: Option<&Arc<Substitution<'a>>>,
+    ) -> Vec<(String, (BrowserId<'a>, Item<'a>))> {
+        let Some((type_id, ty)) = self.resolve_type(type_id) else {
+            return vec![];
+        };
+        let item = Item::Item(ty);
+        use rustdoc_types::ItemEnum::*;
+
+        let mut rows = vec![];
+        let row_id = |group| BrowserId {
+            item: type_id.clone(),
+            group: Some(group),
+            substitution: substitution.cloned(),
+        };
+
+        let auto_impls = self.group_impls(&type_id, Group::AutoTraitImpls);
+        let mut auto_traits = auto_impls
+            .iter()
+            .filter_map(|(_, item)| {
+                let Item::Item(rustdoc_types::Item { inner: Impl(i), .. }) = item else {
+                    return None;
+                };
+                let trait_ = i.trait_.as_ref()?;
+                // Freeze is a compiler implementation detail.
+                if trait_.path == "Freeze" {
+                    return None;
+                }
+                let mark = if i.is_negative {
+                    "✗"
+                } else if i.generics.where_predicates.is_empty() {
+                    "✓"
                 } else {
-                    format!("type {name}")
+                    // Depends on the type's generic parameters.
+                    "?"
                 };
-            }
-        };
-        format!("{prefix} {name}")
+                Some(format!("{} {mark}", trait_.path))
+            })
+            .collect::<Vec<_>>();
+        auto_traits.sort_unstable();
+        if !auto_traits.is_empty() {
+            rows.push((
+                format!("auto traits: {}", auto_traits.join(" ")),
+                (row_id(Group::AutoTraitImpls), item.clone()),
+            ));
+        }
+
+        let blanket_count = self.group_impls(&type_id, Group::BlanketImpls).len();
+        if blanket_count != 0 {
+            rows.push((
+                format!("blanket impls ({blanket_count})"),
+                (row_id(Group::BlanketImpls), item),
+            ));
+        }
+
+        rows
+    }
+
+    fn list_group<'a>(
+        &'a self,
+        type_id: &ItemId<'a>,
+        group: Group,
+        substitution: Option<&Arc<Substitution<'a>>>,
+    ) -> Vec<(String, (BrowserId<'a>, Item<'a>))> {
+        let mut items = self
+            .group_impls(type_id, group)
+            .into_iter()
+            .filter_map(|(id, item)| match item {
+                Item::Item(inner) => {
+                    let substitution = match &inner.inner {
+                        rustdoc_types::ItemEnum::Impl(i) => {
+                            self.impl_substitution(type_id, substitution, i)
+                        }
+                        _ => None,
+                    };
+                    let label = self.substituted_label(id.clone(), inner, substitution.as_ref());
+                    Some((label, (id, inner, substitution)))
+                }
+                Item::Root => None,
+            })
+            .collect::<Vec<_>>();
+        // Sort by trait, before adding the where-clauses.
+        sort_by_label(&mut items);
+        items
+            .into_iter()
+            .map(|(mut label, (id, inner, substitution))| {
+                let substituted = substitution.as_ref().map(|s| s.apply_item(inner));
+                if let rustdoc_types::ItemEnum::Impl(i) =
+                    &substituted.as_ref().unwrap_or(inner).inner
+                {
+                    if let Some(ty) = &i.blanket_impl {
+                        write!(label, " for {}", type_label(ty)).unwrap();
+                    }
+                    label += &where_label(&i.generics);
+                }
+                let id = BrowserId {
+                    substitution,
+                    ..id.into()
+                };
+                (label, (id, Item::Item(inner)))
+            })
+            .collect()
+    }
+
+    /// Lists the items reachable through public paths in the given module, labeled with the names
+    /// they're exported as.
+    fn public_api_items<'a>(
+        &'a self,
+        module_id: &ItemId<'a>,
+        module: &'a rustdoc_types::Module,
+    ) -> Vec<(String, (ItemId<'a>, Item<'a>))> {
+        self.analysis
+            .public_api_items(module_id, module, &mut vec![])
+            .into_iter()
+            .map(|(id, item, rename)| {
+                let label = match rename {
+                    Some(name) => self.named_item_label(id.clone(), item, name),
+                    None => self.item_label(id.clone(), item),
+                };
+                (label, (id, Item::Item(item)))
+            })
+            .collect()
     }
 
     fn single_element_tuple_variant<'a>(
@@ -109,14 +1696,14 @@ impl RustdocBrowser {
 
 impl<'a> Browser for &'a RustdocBrowser {
     type Item = Item<'a>;
-    type ItemId = ItemId<'a>;
+    type ItemId = BrowserId<'a>;
 
-    fn list_crates(&self) -> Vec<(String, ItemId<'a>)> {
+    fn list_crates(&self) -> Vec<(String, BrowserId<'a>)> {
         let mut crates = self
             .analysis
             .crate_ids()
             //.filter(|c| !self.analysis.stdlib_crates.contains(c))
-            .map(|item_id| (crate_label(&item_id), item_id))
+            .map(|item_id| (crate_label(&item_id), item_id.into()))
             .collect::<Vec<_>>();
 
         sort_by_label(&mut crates);
@@ -124,102 +1711,63 @@ impl<'a> Browser for &'a RustdocBrowser {
         crates
     }
 
-    fn list_items(&self, parent_id: &ItemId<'a>) -> Vec<(String, (ItemId<'a>, Item<'a>))> {
-        // If true, skip showing this element's children and show the children of the first child
-        // instead. Basically, skip one level of nesting. Use when the item is redundant.
-        let mut use_first_child = false;
-
-        let mut synthetic_items: Vec<(String, (ItemId<'a>, Item<'a>))> = vec![];
-
-        if let Some((_, Item::Item(parent))) = self.analysis.get_item(parent_id.clone()) {
-            match &parent.inner {
-                rustdoc_types::ItemEnum::Variant(v)
-                    if self
-                        .single_element_tuple_variant(v, parent_id.clone())
-                        .is_some() =>
-                {
-                    // Single-element tuple variant. The only child is a StructField for field "0".
-                    // This adds nothing because the type name is already in the Variant's label.
-                    use_first_child = true;
-                }
-                rustdoc_types::ItemEnum::StructField(_) => {
-                    // StructField's only child is the type, which adds nothing, as the type name is
-                    // already in the StructField's label.
-                    use_first_child = true;
-                }
-                rustdoc_types::ItemEnum::Function(f) => {
-                    synthetic_items = f
-                        .sig
-                        .inputs
-                        .iter()
-                        // TODO: implement special cases for names of self:
-                        //  - self
-                        //  - &self
-                        //  - &mut self
-                        .map(|(name, ty)| (format!("{name}:"), ty))
-                        .chain(f.sig.output.as_ref().map(|ty| ("->".to_owned(), ty)))
-                        .map(|(name, ty)| {
-                            let mut ids = analysis::type_ids(ty)
-                                .into_iter()
-                                .map(|id| parent_id.crate_sibling(id))
-                                .collect::<Vec<_>>();
-                            if ids.is_empty() {
-                                // Inject a fake ID so that the label at least shows up.
-                                ids.push(analysis::EMPTY_ITEM_ID.clone());
-                            }
-                            (name, ty, ids)
-                        })
-                        .flat_map(|(name, ty, ids)| {
-                            let use_suffix = ids.len() > 1;
-                            ids.into_iter().enumerate().map(move |(i, id)| {
-                                let tylabel = type_label(ty);
-                                let mut label = format!("{name} {tylabel}");
-                                if use_suffix {
-                                    label += &format!(" (#{i}");
-                                }
-                                (label, (id, Item::Item(parent)))
-                            })
-                        })
-                        .collect::<Vec<_>>();
-                }
-                _ => (),
+    fn list_items(&self, parent_id: &BrowserId<'a>) -> Vec<(String, (BrowserId<'a>, Item<'a>))> {
+        match parent_id.group {
+            Some(Group::DerefMethods) => {
+                self.list_deref_methods(&parent_id.item, parent_id.substitution.as_ref())
+            }
+            Some(Group::AllMethods) => {
+                self.list_all_methods(&parent_id.item, parent_id.substitution.as_ref())
+            }
+            Some(Group::Constructors) => self.list_constructors(&parent_id.item),
+            Some(Group::Conversions) => self.list_conversions(&parent_id.item),
+            Some(Group::Supertraits) => self.list_supertraits(&parent_id.item),
+            Some(Group::Subtraits) => self.list_subtraits(&parent_id.item),
+            Some(Group::Generics) => {
+                self.list_generics(&parent_id.item, parent_id.substitution.as_ref())
+            }
+            Some(group @ (Group::GenericParam(_) | Group::WherePredicate(_))) => {
+                self.list_generic_bounds(&parent_id.item, group)
             }
+            Some(Group::Macros) => self.list_macros(&parent_id.item),
+            Some(Group::Unsafe) => self.list_unsafe(&parent_id.item),
+            Some(Group::DocCoverage) => self.list_doc_coverage(&parent_id.item),
+            Some(Group::ModuleDocCoverage) => self.list_module_doc_coverage(&parent_id.item),
+            Some(group @ (Group::Dependencies | Group::Dependents)) => {
+                self.list_crate_deps(&parent_id.item, group)
+            }
+            Some(group) => self.list_group(&parent_id.item, group, parent_id.substitution.as_ref()),
+            None => self.list_item_children(&parent_id.item, parent_id.substitution.as_ref()),
         }
+    }
 
-        let mut items = self
-            .analysis
-            .items(parent_id)
-            .filter_map(|(id, item)| {
-                let inner = match item {
-                    Item::Root => return None,
-                    Item::Item(item) => item,
-                };
-
-                // Remove the clutter of blanket, and synthetic trait impls.
-                use rustdoc_types::ItemEnum::*;
-                match &inner.inner {
-                    Impl(i) if i.blanket_impl.is_some() || i.is_synthetic => None,
-                    _ => Some((self.item_label(id.clone(), inner), (id, item))),
-                }
-            })
-            .collect::<Vec<_>>();
-        sort_by_label(&mut items);
-
-        if use_first_child && !items.is_empty() {
-            assert_eq!(
-                items.len(),
-                1,
-                "use_first_child on non-singleton children list: {items:#?}"
-            );
-            return self.list_items(&items[0].1 .0);
-        }
+    fn list_view_options(&self, crate_id: &BrowserId<'a>) -> Vec<(String, bool)> {
+        let opts = self.view_options(crate_id.item.crate_name());
+        vec![
+            (PRIVATE_ITEMS_OPTION.to_owned(), opts.private_items),
+            (HIDDEN_ITEMS_OPTION.to_owned(), opts.hidden_items),
+            (API_VIEW_OPTION.to_owned(), opts.api_view),
+            (RAW_IMPLS_OPTION.to_owned(), opts.raw_impls),
+        ]
+    }
 
-        if !synthetic_items.is_empty() {
-            synthetic_items.extend(items);
-            items = std::mem::take(&mut synthetic_items);
+    fn set_view_option(&self, crate_id: &BrowserId<'a>, option: &str, enabled: bool) {
+        let crate_name = crate_id.item.crate_name();
+        let mut opts = self.view_options(crate_name);
+        match option {
+            PRIVATE_ITEMS_OPTION => opts.private_items = enabled,
+            HIDDEN_ITEMS_OPTION => opts.hidden_items = enabled,
+            API_VIEW_OPTION => opts.api_view = enabled,
+            RAW_IMPLS_OPTION => opts.raw_impls = enabled,
+            _ => {
+                warn!("unknown view option {option:?}");
+                return;
+            }
         }
-
-        items
+        self.view_options
+            .write()
+            .unwrap()
+            .insert(crate_name.to_owned(), opts);
     }
 
     fn get_info(&self, item: &Item<'a>) -> String {
@@ -230,6 +1778,16 @@ impl<'a> Browser for &'a RustdocBrowser {
                     txt += docs;
                     txt.push('\n');
                 }
+                if let Some(deprecation) = &item.deprecation {
+                    writeln!(txt, "{}", deprecation_label(deprecation)).unwrap();
+                }
+                for attr in item.attrs.iter().filter(|attr| is_shown_attr(attr)) {
+                    writeln!(txt, "{attr}").unwrap();
+                }
+                let derives = self.derived_traits(item);
+                if !derives.is_empty() {
+                    writeln!(txt, "derives: {}", derives.join(", ")).unwrap();
+                }
                 if let Some(span) = &item.span {
                     write!(
                         txt,
@@ -238,6 +1796,19 @@ impl<'a> Browser for &'a RustdocBrowser {
                     )
                     .unwrap();
                 }
+                if let Some(layout) = self.type_layout(item) {
+                    write!(txt, "\n\n{layout}").unwrap();
+                }
+                match &item.inner {
+                    rustdoc_types::ItemEnum::Macro(definition) => {
+                        write!(txt, "\n\n{definition}").unwrap();
+                    }
+                    rustdoc_types::ItemEnum::ProcMacro(p) => {
+                        let name = item.name.as_deref().unwrap_or_default();
+                        write!(txt, "\n\n{}", proc_macro_label(p, name)).unwrap();
+                    }
+                    _ => (),
+                }
             }
             Item::Root => {
                 write!(txt, "crate root").unwrap();
@@ -253,21 +1824,154 @@ impl<'a> Browser for &'a RustdocBrowser {
     fn get_source(&self, item: &Item) -> (String, Option<usize>) {
         match item {
             Item::Item(item) => {
-                let (txt, line) = get_source_for_item(item);
+                let (txt, line) = get_source_for_item(item, Path::new(""));
                 (txt, Some(line))
             }
             Item::Root => (String::new(), None),
         }
     }
+
+    fn type_graph(&self, item: &Item<'a>, depth: usize) -> Option<Graph> {
+        let Item::Item(item) = item else {
+            return None;
+        };
+        let id = self.analysis.find_item_id(item)?;
+        let graph = graph::type_graph(&self.analysis, id, depth);
+        (!graph.nodes.is_empty()).then_some(graph)
+    }
+
+    fn supports_crate_graph(&self) -> bool {
+        true
+    }
+
+    fn crate_graph(&self) -> Option<Graph> {
+        Some(graph::crate_graph(&self.analysis))
+    }
+
+    fn output_dir(&self) -> Option<&Path> {
+        self.output_dir.as_deref()
+    }
+
+    fn doc_links(&self, item: &Item<'a>) -> Vec<(String, Option<BrowserId<'a>>)> {
+        let Item::Item(item) = item else {
+            return vec![];
+        };
+        let Some(id) = self.analysis.find_item_id(item) else {
+            return vec![];
+        };
+        let mut links = item
+            .links
+            .iter()
+            .map(|(text, target)| {
+                let target = self
+                    .analysis
+                    .get_item(id.crate_sibling(target))
+                    .map(|(target_id, _)| target_id.into());
+                (text.clone(), target)
+            })
+            .collect::<Vec<_>>();
+        // Put them in the order they're in the docs.
+        let docs = item.docs.as_deref().unwrap_or_default();
+        links.sort_by_key(|(text, _)| (docs.find(text.as_str()), text.clone()));
+        links
+    }
+
+    fn item_path(&self, id: &BrowserId<'a>) -> Option<Vec<BrowserId<'a>>> {
+        let (id, _) = self.analysis.get_item(id.item.clone())?;
+        let mut path = vec![id];
+        while let Some(parent) = self.analysis.parent(path.last().unwrap()) {
+            path.push(parent);
+        }
+        // Everything reachable is under the crate root, which is listed with a special ID.
+        let root = path.pop().unwrap();
+        let Some((_, Item::Item(root_item))) = self.analysis.get_item(root.clone()) else {
+            return None;
+        };
+        if !matches!(&root_item.inner, rustdoc_types::ItemEnum::Module(m) if m.is_crate) {
+            return None;
+        }
+        let (name, _) = self.analysis.crates.get_key_value(root.crate_name())?;
+        path.push(ItemId::crate_root(CrateId { name }));
+        Some(path.into_iter().rev().map(BrowserId::from).collect())
+    }
+
+    fn supports_doc_search(&self) -> bool {
+        true
+    }
+
+    fn search_docs(&self, query: &str) -> Option<Vec<SearchResult<BrowserId<'a>>>> {
+        if query.trim().is_empty() {
+            return Some(vec![]);
+        }
+        let results = self
+            .doc_index
+            .search(&self.analysis, query)
+            .into_iter()
+            .map(|m| SearchResult {
+                id: m.id.into(),
+                label: format!("{} {}", m.kind, m.path),
+                detail: m.snippet,
+                highlight: Some(m.highlight),
+            })
+            .collect();
+        Some(results)
+    }
+
+    fn supports_signature_search(&self) -> bool {
+        true
+    }
+
+    fn search_signatures(
+        &self,
+        query: &str,
+    ) -> Option<anyhow::Result<Vec<SearchResult<BrowserId<'a>>>>> {
+        if query.trim().is_empty() {
+            return Some(Ok(vec![]));
+        }
+        let query = match query.parse::<SignatureQuery>() {
+            Ok(query) => query,
+            Err(e) => return Some(Err(e)),
+        };
+        let results = type_search::search(&self.analysis, &query)
+            .into_iter()
+            .map(|m| SearchResult {
+                id: m.id.into(),
+                label: m.path,
+                detail: m.signature,
+                highlight: None,
+            })
+            .collect();
+        Some(Ok(results))
+    }
+
+    fn supports_impl_query(&self) -> bool {
+        true
+    }
+
+    fn query_impl(
+        &self,
+        type_path: &str,
+        trait_path: &str,
+    ) -> Option<anyhow::Result<ImplAnswer<BrowserId<'a>>>> {
+        if type_path.trim().is_empty() && trait_path.trim().is_empty() {
+            return Some(Ok(ImplAnswer {
+                summary: String::new(),
+                impls: vec![],
+            }));
+        }
+        Some(self.impl_answer(type_path, trait_path))
+    }
 }
 
-fn get_source_for_item(item: &rustdoc_types::Item) -> (String, usize) {
+/// Read the source file an item is defined in, with line numbers, and return it along with the
+/// (zero-based) line the item starts on. Relative source paths are looked up under `root`.
+pub(crate) fn get_source_for_item(item: &rustdoc_types::Item, root: &Path) -> (String, usize) {
     use std::fs::File;
     use std::io::{BufRead, BufReader};
     let Some(span) = &item.span else {
         return (String::new(), 0);
     };
-    match File::open(&span.filename) {
+    match File::open(root.join(&span.filename)) {
         Ok(f) => {
             let mut txt = String::new();
             for (i, line) in BufReader::new(f).lines().enumerate() {
@@ -283,6 +1987,9 @@ fn get_source_for_item(item: &rustdoc_types::Item) -> (String, usize) {
 }
 
 fn cmp_labels(a: &str, b: &str) -> std::cmp::Ordering {
+    // Visibility doesn't affect the order.
+    let a = strip_visibility(a);
+    let b = strip_visibility(b);
     // Fields (assuming they contain ": ") go first
     a.contains(": ")
         .cmp(&b.contains(": "))
@@ -290,17 +1997,92 @@ fn cmp_labels(a: &str, b: &str) -> std::cmp::Ordering {
         .then_with(|| a.cmp(b))
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
+/// Removes a leading visibility marker (as added by `RustdocBrowser::item_label`) from a label.
+fn strip_visibility(label: &str) -> &str {
+    if label.starts_with("pub(") {
+        if let Some(end) = label.find(") ") {
+            return &label[end + 2..];
+        }
+    }
+    label.strip_prefix("pub ").unwrap_or(label)
+}
 
-    #[test]
-    fn cmp_test() {
-        use std::cmp::Ordering::*;
-        assert_eq!(cmp_labels("a: a", "b: b"), Less);
-        assert_eq!(cmp_labels("a", "z: z"), Greater);
-        assert_eq!(cmp_labels("a", "b"), Less);
+/// How a method takes `self`, if it does.
+fn receiver_label(f: &rustdoc_types::Function) -> Option<String> {
+    use rustdoc_types::Type;
+    let (name, ty) = f.sig.inputs.first()?;
+    if name != "self" {
+        return None;
+    }
+    Some(match ty {
+        Type::Generic(s) if s == "Self" => "self".to_owned(),
+        Type::BorrowedRef {
+            is_mutable, type_, ..
+        } if matches!(&**type_, Type::Generic(s) if s == "Self") => {
+            if *is_mutable {
+                "&mut self".to_owned()
+            } else {
+                "&self".to_owned()
+            }
+        }
+        _ => format!("self: {}", type_label(ty)),
+    })
+}
+
+/// Label for a procedural macro, in the form it's invoked in, plus any helper attributes.
+fn proc_macro_label(p: &rustdoc_types::ProcMacro, name: &str) -> String {
+    let mut label = match p.kind {
+        rustdoc_types::MacroKind::Bang => format!("proc macro {name}!"),
+        rustdoc_types::MacroKind::Attr => format!("attribute macro #[{name}]"),
+        rustdoc_types::MacroKind::Derive => format!("derive macro {name}"),
+    };
+    if !p.helpers.is_empty() {
+        // Helper attributes, e.g. "(#[serde], #[skip])".
+        let helpers = p.helpers.iter().map(|h| format!("#[{h}]"));
+        write!(label, " ({})", helpers.collect::<Vec<_>>().join(", ")).unwrap();
+    }
+    label
+}
+
+/// Which group an impl is listed in instead of directly under its type, if any.
+fn group_of_impl(i: &rustdoc_types::Impl) -> Option<Group> {
+    if i.is_synthetic {
+        Some(Group::AutoTraitImpls)
+    } else if i.blanket_impl.is_some() {
+        Some(Group::BlanketImpls)
+    } else {
+        None
+    }
+}
+
+fn deprecation_label(deprecation: &rustdoc_types::Deprecation) -> String {
+    let mut label = "⚠ deprecated".to_owned();
+    if let Some(since) = &deprecation.since {
+        write!(label, " since {since}").unwrap();
     }
+    if let Some(note) = &deprecation.note {
+        write!(label, ": {note}").unwrap();
+    }
+    label
+}
+
+/// Whether an attribute says something about the item worth showing in its info. Lint levels and
+/// the standard library's internal attributes are left out.
+fn is_shown_attr(attr: &str) -> bool {
+    const HIDDEN_PREFIXES: &[&str] = &[
+        "#[allow(",
+        "#[warn(",
+        "#[deny(",
+        "#[forbid(",
+        "#[expect(",
+        "#[rustc_",
+        "#[stable(",
+        "#[unstable(",
+        "#[automatically_derived]",
+    ];
+    !HIDDEN_PREFIXES
+        .iter()
+        .any(|prefix| attr.starts_with(prefix))
 }
 
 fn sort_by_label<T>(slice: &mut [(String, T)]) {
@@ -311,7 +2093,7 @@ fn crate_label(id: &ItemId) -> String {
     id.crate_name().to_owned()
 }
 
-fn generic_label(g: &rustdoc_types::GenericArgs) -> String {
+pub(crate) fn generic_label(g: &rustdoc_types::GenericArgs) -> String {
     use rustdoc_types::{GenericArg, GenericArgs};
     use std::borrow::Cow;
     let mut s = String::new();
@@ -357,7 +2139,7 @@ fn generic_label(g: &rustdoc_types::GenericArgs) -> String {
     s
 }
 
-fn type_label(ty: &rustdoc_types::Type) -> String {
+pub(crate) fn type_label(ty: &rustdoc_types::Type) -> String {
     use rustdoc_types::Type::*;
     match ty {
         ResolvedPath(p) => {
@@ -415,7 +2197,7 @@ fn type_label(ty: &rustdoc_types::Type) -> String {
                         TraitBound { trait_, .. } => trait_.path.clone(),
                         Outlives(o) => o.clone(),
                         Use(u) => {
-                            u.into_iter()
+                            u.iter()
                                 .map(|p| match p {
                                     Lifetime(s) => s,
                                     Param(s) => s,
@@ -459,7 +2241,8 @@ fn type_label(ty: &rustdoc_types::Type) -> String {
             self_type,
             trait_,
         } => {
-            if let Some(trait_) = trait_ {
+            // rustdoc gives `Self::Assoc` an empty trait path.
+            if let Some(trait_) = trait_.as_ref().filter(|t| !t.path.is_empty()) {
                 format!("<{} as {}>::{name}", type_label(self_type), trait_.path)
             } else {
                 format!("{}::{name}", type_label(self_type))
@@ -468,3 +2251,189 @@ fn type_label(ty: &rustdoc_types::Type) -> String {
         Pat { type_, .. } => type_label(type_),
     }
 }
+
+pub(crate) fn bound_label(b: &rustdoc_types::GenericBound) -> String {
+    use rustdoc_types::GenericBound::*;
+    match b {
+        TraitBound {
+            trait_,
+            generic_params,
+            modifier,
+        } => {
+            let mut s = String::new();
+            if !generic_params.is_empty() {
+                s.push_str(&format!("for<{}> ", generic_params_label(generic_params)));
+            }
+            match modifier {
+                rustdoc_types::TraitBoundModifier::None => (),
+                rustdoc_types::TraitBoundModifier::Maybe => s.push('?'),
+                rustdoc_types::TraitBoundModifier::MaybeConst => s.push_str("~const "),
+            }
+            s.push_str(&trait_.path);
+            if let Some(args) = &trait_.args {
+                s.push_str(&generic_label(args));
+            }
+            s
+        }
+        Outlives(lifetime) => lifetime.clone(),
+        Use(args) => format!(
+            "use<{}>",
+            args.iter()
+                .map(|arg| match arg {
+                    rustdoc_types::PreciseCapturingArg::Lifetime(s) => s.as_str(),
+                    rustdoc_types::PreciseCapturingArg::Param(s) => s.as_str(),
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+pub(crate) fn bounds_label(bounds: &[rustdoc_types::GenericBound]) -> String {
+    bounds
+        .iter()
+        .map(bound_label)
+        .collect::<Vec<_>>()
+        .join(" + ")
+}
+
+pub(crate) fn generic_param_label(p: &rustdoc_types::GenericParamDef) -> String {
+    use rustdoc_types::GenericParamDefKind::*;
+    let mut s = String::new();
+    match &p.kind {
+        Lifetime { outlives } => {
+            s.push_str(&p.name);
+            if !outlives.is_empty() {
+                s.push_str(": ");
+                s.push_str(&outlives.join(" + "));
+            }
+        }
+        Type {
+            bounds, default, ..
+        } => {
+            s.push_str(&p.name);
+            if !bounds.is_empty() {
+                s.push_str(": ");
+                s.push_str(&bounds_label(bounds));
+            }
+            if let Some(ty) = default {
+                s.push_str(" = ");
+                s.push_str(&type_label(ty));
+            }
+        }
+        Const { type_, default } => {
+            write!(s, "const {}: {}", p.name, type_label(type_)).unwrap();
+            if let Some(default) = default {
+                s.push_str(" = ");
+                s.push_str(default);
+            }
+        }
+    }
+    s
+}
+
+fn generic_params_label(params: &[rustdoc_types::GenericParamDef]) -> String {
+    params
+        .iter()
+        // Leave out the parameters generated for `impl Trait` arguments.
+        .filter(|p| {
+            !matches!(
+                p.kind,
+                rustdoc_types::GenericParamDefKind::Type {
+                    is_synthetic: true,
+                    ..
+                }
+            )
+        })
+        .map(generic_param_label)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The generic parameters of an item, as written in angle brackets after its name.
+pub(crate) fn generics_label(g: &rustdoc_types::Generics) -> String {
+    let params = generic_params_label(&g.params);
+    if params.is_empty() {
+        params
+    } else {
+        format!("<{params}>")
+    }
+}
+
+pub(crate) fn where_predicate_label(pred: &rustdoc_types::WherePredicate) -> String {
+    use rustdoc_types::WherePredicate::*;
+    match pred {
+        BoundPredicate {
+            type_,
+            bounds,
+            generic_params,
+        } => {
+            let mut s = String::new();
+            if !generic_params.is_empty() {
+                s.push_str(&format!("for<{}> ", generic_params_label(generic_params)));
+            }
+            write!(s, "{}: {}", type_label(type_), bounds_label(bounds)).unwrap();
+            s
+        }
+        LifetimePredicate { lifetime, outlives } => {
+            format!("{lifetime}: {}", outlives.join(" + "))
+        }
+        EqPredicate { lhs, rhs } => {
+            let rhs = match rhs {
+                rustdoc_types::Term::Type(ty) => type_label(ty),
+                rustdoc_types::Term::Constant(c) => c.expr.clone(),
+            };
+            format!("{} == {rhs}", type_label(lhs))
+        }
+    }
+}
+
+/// The where-clause of an item, including a leading space, or an empty string if it has none.
+pub(crate) fn where_label(g: &rustdoc_types::Generics) -> String {
+    if g.where_predicates.is_empty() {
+        return String::new();
+    }
+    format!(
+        " where {}",
+        g.where_predicates
+            .iter()
+            .map(where_predicate_label)
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cmp_test() {
+        use std::cmp::Ordering::*;
+        assert_eq!(cmp_labels("a: a", "b: b"), Less);
+        assert_eq!(cmp_labels("a", "z: z"), Greater);
+        assert_eq!(cmp_labels("a", "b"), Less);
+        assert_eq!(cmp_labels("pub b", "a"), Greater);
+        assert_eq!(cmp_labels("pub(crate) a", "pub b"), Less);
+        assert_eq!(cmp_labels("pub(in crate::x) a: a", "b"), Less);
+    }
+
+    #[test]
+    fn qualified_path_label() {
+        let assoc = |trait_path: &str| rustdoc_types::Type::QualifiedPath {
+            name: "Err".to_owned(),
+            args: Box::new(rustdoc_types::GenericArgs::AngleBracketed {
+                args: vec![],
+                constraints: vec![],
+            }),
+            self_type: Box::new(rustdoc_types::Type::Generic("Self".to_owned())),
+            trait_: Some(rustdoc_types::Path {
+                path: trait_path.to_owned(),
+                id: rustdoc_types::Id(0),
+                args: None,
+            }),
+        };
+        assert_eq!(type_label(&assoc("FromStr")), "<Self as FromStr>::Err");
+        assert_eq!(type_label(&assoc("")), "Self::Err");
+    }
+}
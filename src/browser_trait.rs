@@ -1,10 +1,97 @@
+pub use crate::graph::{Format, Graph};
+use std::ops::Range;
+use std::path::Path;
+
+/// An item found by a search.
+#[derive(Debug, Clone)]
+pub struct SearchResult<Id> {
+    pub id: Id,
+    /// What the item is, e.g. its kind and path.
+    pub label: String,
+    /// Why it matched, e.g. the part of its docs that did.
+    pub detail: String,
+    /// Part of `detail` to highlight.
+    pub highlight: Option<Range<usize>>,
+}
+
+/// The answer to whether a type implements a trait.
+#[derive(Debug, Clone)]
+pub struct ImplAnswer<Id> {
+    /// A sentence saying whether it does.
+    pub summary: String,
+    /// The impls that show it.
+    pub impls: Vec<SearchResult<Id>>,
+}
+
 pub trait Browser {
     type Item: Clone + Send + Sync;
-    type ItemId: Clone + Send + Sync;
+    type ItemId: Clone + PartialEq + Send + Sync;
     fn list_crates(&self) -> Vec<(String, Self::ItemId)>;
     #[allow(clippy::type_complexity)]
     fn list_items(&self, parent_id: &Self::ItemId) -> Vec<(String, (Self::ItemId, Self::Item))>;
+    /// Named options controlling what gets listed for the crate containing the given item, and
+    /// whether each is currently enabled.
+    fn list_view_options(&self, crate_id: &Self::ItemId) -> Vec<(String, bool)>;
+    fn set_view_option(&self, crate_id: &Self::ItemId, option: &str, enabled: bool);
     fn get_info(&self, item: &Self::Item) -> String;
     fn get_debug_info(&self, item: &Self::Item) -> String;
     fn get_source(&self, item: &Self::Item) -> (String, Option<usize>);
+    /// A graph of the types related to the given item, up to `depth` steps away, if it's a type
+    /// or trait.
+    fn type_graph(&self, _item: &Self::Item, _depth: usize) -> Option<Graph> {
+        None
+    }
+    /// Whether `crate_graph` is supported.
+    fn supports_crate_graph(&self) -> bool {
+        false
+    }
+    /// A graph of how the crates being browsed depend on each other.
+    fn crate_graph(&self) -> Option<Graph> {
+        None
+    }
+    /// Where to save files exported from the browser, like graphs, if not the current directory.
+    fn output_dir(&self) -> Option<&Path> {
+        None
+    }
+    /// The intra-doc links in the given item's docs: the text of each, and the item it links to,
+    /// if that could be found.
+    fn doc_links(&self, _item: &Self::Item) -> Vec<(String, Option<Self::ItemId>)> {
+        vec![]
+    }
+    /// The IDs to select in each pane to get to the given item, starting with its crate.
+    fn item_path(&self, _id: &Self::ItemId) -> Option<Vec<Self::ItemId>> {
+        None
+    }
+    /// Whether `search_docs` is supported.
+    fn supports_doc_search(&self) -> bool {
+        false
+    }
+    /// Search the docs of every item, if supported.
+    fn search_docs(&self, _query: &str) -> Option<Vec<SearchResult<Self::ItemId>>> {
+        None
+    }
+    /// Whether `search_signatures` is supported.
+    fn supports_signature_search(&self) -> bool {
+        false
+    }
+    /// Search for functions by the types they take and return, like `&str -> String`, if
+    /// supported.
+    fn search_signatures(
+        &self,
+        _query: &str,
+    ) -> Option<anyhow::Result<Vec<SearchResult<Self::ItemId>>>> {
+        None
+    }
+    /// Whether `query_impl` is supported.
+    fn supports_impl_query(&self) -> bool {
+        false
+    }
+    /// Whether the type at the given path implements the trait at the other path, if supported.
+    fn query_impl(
+        &self,
+        _type_path: &str,
+        _trait_path: &str,
+    ) -> Option<anyhow::Result<ImplAnswer<Self::ItemId>>> {
+        None
+    }
 }
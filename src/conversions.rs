@@ -0,0 +1,183 @@
+//! Which types a type can be converted from and into, by the conversion traits' impls.
+
+use std::collections::HashMap;
+
+use crate::analysis::{Analysis, CrateId, Item, ItemId};
+
+/// The traits that convert between types, by full path.
+const CONVERSION_TRAITS: &[&[&str]] = &[
+    &["core", "convert", "From"],
+    &["core", "convert", "TryFrom"],
+    &["core", "convert", "Into"],
+    &["core", "convert", "AsRef"],
+    &["core", "borrow", "Borrow"],
+    &["core", "str", "traits", "FromStr"],
+];
+
+/// The impls of conversion traits in all the loaded crates, by the types they convert.
+#[derive(Debug, Default)]
+pub struct ConversionIndex {
+    crate_names: Vec<String>,
+    /// Full paths of types, and the impls converting them, by index into `crate_names` and ID.
+    impls: HashMap<Vec<String>, Vec<(usize, rustdoc_types::Id)>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Direction {
+    /// The type can be made from the other one.
+    From,
+    /// The type can be turned into (or, for `AsRef` and `Borrow`, borrowed as) the other one.
+    Into,
+}
+
+#[derive(Debug, Clone)]
+pub struct Conversion<'a> {
+    pub direction: Direction,
+    /// The other type, or `None` for `FromStr`, which converts from `str`.
+    pub other: Option<&'a rustdoc_types::Type>,
+    /// The name of the conversion trait, e.g. "TryFrom".
+    pub trait_name: &'a str,
+    /// The impl the conversion comes from.
+    pub impl_id: ItemId<'a>,
+    pub impl_: &'a rustdoc_types::Impl,
+}
+
+impl Conversion<'_> {
+    /// Whether the conversion borrows the other type rather than making one.
+    pub fn is_borrow(&self) -> bool {
+        matches!(self.trait_name, "AsRef" | "Borrow")
+    }
+}
+
+impl ConversionIndex {
+    pub fn build(analysis: &Analysis) -> Self {
+        let mut crate_names = analysis.crates.keys().cloned().collect::<Vec<_>>();
+        crate_names.sort();
+        let mut impls: HashMap<Vec<String>, Vec<(usize, rustdoc_types::Id)>> = HashMap::new();
+        for (crate_idx, name) in crate_names.iter().enumerate() {
+            let crate_ = &analysis.crates[name];
+            let root = ItemId::crate_root(CrateId { name });
+            for (id, item) in &crate_.index {
+                let Some((_, source, target, _)) =
+                    conversion_impl(analysis, &root.crate_sibling(id), item)
+                else {
+                    continue;
+                };
+                for ty in source.into_iter().chain(target) {
+                    let rustdoc_types::Type::ResolvedPath(p) = ty else {
+                        continue;
+                    };
+                    if let Some(summary) = crate_.paths.get(&p.id) {
+                        let ids = impls.entry(summary.path.clone()).or_default();
+                        if !ids.contains(&(crate_idx, *id)) {
+                            ids.push((crate_idx, *id));
+                        }
+                    }
+                }
+            }
+        }
+        Self { crate_names, impls }
+    }
+
+    /// Find the impls of conversion traits in all loaded crates which convert the given type from
+    /// or into another type, either with the type as the impl's `Self` type or as the trait's
+    /// argument. Blanket impls, like `impl<T> From<T> for T`, are left out.
+    pub fn conversions<'a>(
+        &self,
+        analysis: &'a Analysis,
+        type_id: &ItemId<'a>,
+    ) -> Vec<Conversion<'a>> {
+        let Some(type_path) = analysis.get_path(type_id.clone(), "conversions") else {
+            return vec![];
+        };
+        let is_type = |impl_id: &ItemId, ty: &rustdoc_types::Type| match ty {
+            rustdoc_types::Type::ResolvedPath(p) => analysis.is_path_to(impl_id, p, type_path),
+            _ => false,
+        };
+
+        let mut conversions = vec![];
+        for (crate_idx, id) in self.impls.get(type_path).into_iter().flatten() {
+            let Some((name, crate_)) = analysis.crates.get_key_value(&self.crate_names[*crate_idx])
+            else {
+                continue;
+            };
+            let Some((id, item)) = crate_.index.get_key_value(id) else {
+                continue;
+            };
+            let impl_id = ItemId::crate_root(CrateId { name }).crate_sibling(id);
+            let Some((trait_name, source, target, impl_)) =
+                conversion_impl(analysis, &impl_id, item)
+            else {
+                continue;
+            };
+            let conversion = |direction, other| Conversion {
+                direction,
+                other,
+                trait_name,
+                impl_id: impl_id.clone(),
+                impl_,
+            };
+            if target.is_some_and(|ty| is_type(&impl_id, ty)) {
+                if source.is_none_or(|ty| !is_type(&impl_id, ty)) {
+                    conversions.push(conversion(Direction::From, source));
+                }
+            } else if source.is_some_and(|ty| is_type(&impl_id, ty)) {
+                conversions.push(conversion(Direction::Into, target));
+            }
+        }
+        conversions
+    }
+}
+
+/// If the item is a (non-blanket) impl of a conversion trait, the name of the trait, the type it
+/// converts from, and the one it converts into. `FromStr` converts from no type in particular.
+#[allow(clippy::type_complexity)]
+fn conversion_impl<'a>(
+    analysis: &Analysis,
+    impl_id: &ItemId,
+    item: &'a rustdoc_types::Item,
+) -> Option<(
+    &'static str,
+    Option<&'a rustdoc_types::Type>,
+    Option<&'a rustdoc_types::Type>,
+    &'a rustdoc_types::Impl,
+)> {
+    let rustdoc_types::ItemEnum::Impl(impl_) = &item.inner else {
+        return None;
+    };
+    if item.crate_id != 0 || impl_.blanket_impl.is_some() {
+        return None;
+    }
+    let trait_ = impl_.trait_.as_ref()?;
+    let trait_path = CONVERSION_TRAITS
+        .iter()
+        .find(|path| analysis.is_path_to(impl_id, trait_, path))?;
+    let trait_name = *trait_path.last()?;
+    let arg = match trait_.args.as_deref() {
+        Some(rustdoc_types::GenericArgs::AngleBracketed { args, .. }) => match args.first() {
+            Some(rustdoc_types::GenericArg::Type(ty)) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    };
+    let (source, target) = match trait_name {
+        "From" | "TryFrom" => (arg, Some(&impl_.for_)),
+        "FromStr" => (None, Some(&impl_.for_)),
+        _ => (Some(&impl_.for_), arg),
+    };
+    Some((trait_name, source, target, impl_))
+}
+
+/// The item a conversion's other type refers to, if it's one that can be browsed.
+pub fn other_item<'a>(
+    analysis: &'a Analysis,
+    conversion: &Conversion<'a>,
+) -> Option<(ItemId<'a>, &'a rustdoc_types::Item)> {
+    let rustdoc_types::Type::ResolvedPath(p) = conversion.other? else {
+        return None;
+    };
+    match analysis.get_item(conversion.impl_id.crate_sibling(&p.id))? {
+        (id, Item::Item(item)) => Some((id, item)),
+        _ => None,
+    }
+}
@@ -0,0 +1,455 @@
+//! Which public items of the workspace's crates have doc comments.
+
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Write};
+
+use crate::analysis::{self, Analysis, CrateId, Item, ItemId};
+
+#[derive(Debug, Clone)]
+pub struct CrateCoverage<'a> {
+    pub crate_name: &'a str,
+    /// Modules with any public items, sorted by path.
+    pub modules: Vec<ModuleCoverage<'a>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ModuleCoverage<'a> {
+    pub id: ItemId<'a>,
+    pub path: String,
+    /// Public items declared in the module, including fields, variants and methods of the types
+    /// in it, sorted by name. A module itself is counted in the module containing it, except for
+    /// the crate root, which is counted in itself.
+    pub items: Vec<CoverageItem<'a>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CoverageItem<'a> {
+    pub id: ItemId<'a>,
+    pub item: &'a rustdoc_types::Item,
+    /// What the item is and its path within the module, e.g. "fn Type::method".
+    pub label: String,
+    pub documented: bool,
+}
+
+/// Counts of documented and total items, for anything made up of them.
+pub trait Coverage {
+    fn documented(&self) -> usize;
+    fn total(&self) -> usize;
+
+    /// Percent of the items that are documented; 100 if there are none.
+    fn percent(&self) -> f64 {
+        match self.total() {
+            0 => 100.,
+            total => self.documented() as f64 * 100. / total as f64,
+        }
+    }
+}
+
+impl Coverage for ModuleCoverage<'_> {
+    fn documented(&self) -> usize {
+        self.items.iter().filter(|item| item.documented).count()
+    }
+
+    fn total(&self) -> usize {
+        self.items.len()
+    }
+}
+
+impl Coverage for CrateCoverage<'_> {
+    fn documented(&self) -> usize {
+        self.modules.iter().map(Coverage::documented).sum()
+    }
+
+    fn total(&self) -> usize {
+        self.modules.iter().map(Coverage::total).sum()
+    }
+}
+
+impl<T: Coverage> Coverage for [T] {
+    fn documented(&self) -> usize {
+        self.iter().map(Coverage::documented).sum()
+    }
+
+    fn total(&self) -> usize {
+        self.iter().map(Coverage::total).sum()
+    }
+}
+
+/// Find the public items declared in a crate, reachable from its root, and whether they have docs,
+/// grouped by module.
+pub fn crate_coverage<'a>(analysis: &'a Analysis, crate_name: &str) -> CrateCoverage<'a> {
+    let Some((name, crate_)) = analysis.crates.get_key_value(crate_name) else {
+        return CrateCoverage {
+            crate_name: "",
+            modules: vec![],
+        };
+    };
+    let root = ItemId::crate_root(CrateId { name });
+    let root_module = root.crate_sibling(&crate_.root);
+
+    let mut items = vec![];
+    if let Some((_, Item::Item(root_item))) = analysis.get_item(root_module.clone()) {
+        let mut walk = Walk {
+            analysis,
+            crate_name: name,
+            visited_modules: vec![],
+            seen: HashSet::new(),
+            items: &mut items,
+        };
+        walk.add_reachable(root_module.clone(), root_item);
+    }
+
+    let mut modules: Vec<ModuleCoverage> = vec![];
+    for (id, item) in items {
+        if !counts(item) {
+            continue;
+        }
+        let module_id = if id == root_module {
+            root_module.clone()
+        } else {
+            let Some(module_id) = analysis.containing_module(&id) else {
+                continue;
+            };
+            module_id
+        };
+        let coverage_item = coverage_item(analysis, id, item);
+        match modules.iter_mut().find(|module| module.id == module_id) {
+            Some(module) => module.items.push(coverage_item),
+            None => modules.push(ModuleCoverage {
+                path: module_path(analysis, &module_id),
+                id: module_id,
+                items: vec![coverage_item],
+            }),
+        }
+    }
+
+    for module in &mut modules {
+        module.items.sort_by(|a, b| a.label.cmp(&b.label));
+    }
+    modules.sort_by(|a, b| a.path.cmp(&b.path));
+    CrateCoverage {
+        crate_name: name,
+        modules,
+    }
+}
+
+fn coverage_item<'a>(
+    analysis: &Analysis,
+    id: ItemId<'a>,
+    item: &'a rustdoc_types::Item,
+) -> CoverageItem<'a> {
+    CoverageItem {
+        label: format!(
+            "{} {}",
+            analysis::kind_name(item),
+            analysis.name_in_module(&id, item)
+        ),
+        documented: item
+            .docs
+            .as_ref()
+            .is_some_and(|docs| !docs.trim().is_empty()),
+        id,
+        item,
+    }
+}
+
+fn module_path(analysis: &Analysis, module_id: &ItemId) -> String {
+    analysis
+        .get_path(module_id.clone(), "module")
+        .map(|path| path.join("::"))
+        .unwrap_or_else(|| module_id.crate_name().to_owned())
+}
+
+/// The coverage of each workspace crate, found once so it can be shown again without walking the
+/// crate's public API.
+#[derive(Debug, Default)]
+pub struct CoverageIndex {
+    /// Each crate's modules, in the order `crate_coverage` gives them, by crate name.
+    crates: HashMap<String, Vec<IndexedModule>>,
+}
+
+#[derive(Debug)]
+struct IndexedModule {
+    id: rustdoc_types::Id,
+    items: Vec<rustdoc_types::Id>,
+    documented: usize,
+}
+
+impl Coverage for IndexedModule {
+    fn documented(&self) -> usize {
+        self.documented
+    }
+
+    fn total(&self) -> usize {
+        self.items.len()
+    }
+}
+
+impl CoverageIndex {
+    pub fn build(analysis: &Analysis) -> Self {
+        let crates = workspace_coverage(analysis)
+            .into_iter()
+            .map(|crate_| {
+                let modules = crate_
+                    .modules
+                    .iter()
+                    .map(|module| IndexedModule {
+                        id: *module.id.id(),
+                        items: module.items.iter().map(|item| *item.id.id()).collect(),
+                        documented: module.documented(),
+                    })
+                    .collect();
+                (crate_.crate_name.to_owned(), modules)
+            })
+            .collect();
+        Self { crates }
+    }
+
+    /// Percent of a crate's public items that are documented.
+    pub fn percent(&self, crate_name: &str) -> f64 {
+        self.crates
+            .get(crate_name)
+            .map_or(100., |modules| modules.percent())
+    }
+
+    /// A crate's coverage, the same as `crate_coverage` finds.
+    pub fn crate_coverage<'a>(
+        &self,
+        analysis: &'a Analysis,
+        crate_name: &str,
+    ) -> CrateCoverage<'a> {
+        let (Some(modules), Some((name, _))) = (
+            self.crates.get(crate_name),
+            analysis.crates.get_key_value(crate_name),
+        ) else {
+            return CrateCoverage {
+                crate_name: "",
+                modules: vec![],
+            };
+        };
+        CrateCoverage {
+            crate_name: name,
+            modules: modules
+                .iter()
+                .filter_map(|module| self.module(analysis, crate_name, module))
+                .collect(),
+        }
+    }
+
+    /// The coverage of one of a crate's modules.
+    pub fn module_coverage<'a>(
+        &self,
+        analysis: &'a Analysis,
+        module_id: &ItemId,
+    ) -> Option<ModuleCoverage<'a>> {
+        let module = self
+            .crates
+            .get(module_id.crate_name())?
+            .iter()
+            .find(|module| module.id == *module_id.id())?;
+        self.module(analysis, module_id.crate_name(), module)
+    }
+
+    fn module<'a>(
+        &self,
+        analysis: &'a Analysis,
+        crate_name: &str,
+        module: &IndexedModule,
+    ) -> Option<ModuleCoverage<'a>> {
+        let (name, crate_) = analysis.crates.get_key_value(crate_name)?;
+        let root = ItemId::crate_root(CrateId { name });
+        let (module_id, _) = crate_.index.get_key_value(&module.id)?;
+        let module_id = root.crate_sibling(module_id);
+        let items = module
+            .items
+            .iter()
+            .filter_map(|id| {
+                let (id, item) = crate_.index.get_key_value(id)?;
+                Some(coverage_item(analysis, root.crate_sibling(id), item))
+            })
+            .collect();
+        Some(ModuleCoverage {
+            path: module_path(analysis, &module_id),
+            id: module_id,
+            items,
+        })
+    }
+}
+
+/// Coverage of each of the workspace's crates, sorted by name.
+pub fn workspace_coverage(analysis: &Analysis) -> Vec<CrateCoverage<'_>> {
+    let mut crate_names = analysis
+        .crates
+        .keys()
+        .filter(|name| analysis.is_workspace_crate(name))
+        .collect::<Vec<_>>();
+    crate_names.sort();
+    crate_names
+        .into_iter()
+        .map(|name| crate_coverage(analysis, name))
+        .collect()
+}
+
+/// State for collecting the items declared in a crate which are reachable from outside it.
+struct Walk<'a, 'b> {
+    analysis: &'a Analysis,
+    crate_name: &'a str,
+    visited_modules: Vec<ItemId<'a>>,
+    seen: HashSet<ItemId<'a>>,
+    items: &'b mut Vec<(ItemId<'a>, &'a rustdoc_types::Item)>,
+}
+
+impl<'a> Walk<'a, '_> {
+    /// Add an item, and what's reachable through it: the items a module exports, following
+    /// re-exports, and the public members of types, variants and traits. Items from other crates,
+    /// and `#[doc(hidden)]` items and everything in them, are left out.
+    fn add_reachable(&mut self, id: ItemId<'a>, item: &'a rustdoc_types::Item) {
+        if analysis::is_doc_hidden(item)
+            || item.crate_id != 0
+            || item.span.is_none()
+            || id.crate_name() != self.crate_name
+            || !self.seen.insert(id.clone())
+        {
+            return;
+        }
+        self.items.push((id.clone(), item));
+
+        use rustdoc_types::ItemEnum::*;
+        let children = match &item.inner {
+            Module(m) => self
+                .analysis
+                .public_api_items(&id, m, &mut self.visited_modules)
+                .into_iter()
+                .map(|(child_id, child, _)| (child_id, child))
+                .collect(),
+            Struct(_) | Union(_) | Enum(_) | Variant(_) | Trait(_) => {
+                let mut children = vec![];
+                for (child_id, child) in item_children(self.analysis, &id) {
+                    match &child.inner {
+                        // Methods of inherent impls are members of the type.
+                        Impl(i) if i.trait_.is_none() && !analysis::is_doc_hidden(child) => {
+                            children
+                                .extend(item_children(self.analysis, &child_id).filter(
+                                    |(_, member)| analysis::is_public(member, Some(child)),
+                                ));
+                        }
+                        Impl(_) => (),
+                        _ if analysis::is_public(child, Some(item)) => {
+                            children.push((child_id, child))
+                        }
+                        _ => (),
+                    }
+                }
+                children
+            }
+            _ => vec![],
+        };
+        for (child_id, child) in children {
+            self.add_reachable(child_id, child);
+        }
+    }
+}
+
+fn item_children<'a, 'b>(
+    analysis: &'a Analysis,
+    id: &'b ItemId<'a>,
+) -> impl Iterator<Item = (ItemId<'a>, &'a rustdoc_types::Item)> + 'b
+where
+    'a: 'b,
+{
+    analysis.items(id).filter_map(|(id, item)| match item {
+        Item::Item(item) => Some((id, item)),
+        Item::Root => None,
+    })
+}
+
+/// Whether a public item is expected to have docs. Like the `missing_docs` lint, tuple fields and
+/// items in trait impls don't count.
+fn counts(item: &rustdoc_types::Item) -> bool {
+    use rustdoc_types::ItemEnum::*;
+    if matches!(
+        item.inner,
+        Impl(_) | Use(_) | ExternCrate { .. } | Primitive(_)
+    ) {
+        return false;
+    }
+    !(matches!(item.inner, StructField(_))
+        && item
+            .name
+            .as_ref()
+            .is_some_and(|name| name.parse::<usize>().is_ok()))
+}
+
+/// Print the coverage of each crate and module, and the undocumented items in each module.
+pub fn write_text(crates: &[CrateCoverage], out: &mut impl Write) -> io::Result<()> {
+    for crate_ in crates {
+        writeln!(
+            out,
+            "{}: {}/{} documented ({:.1}%)",
+            crate_.crate_name,
+            crate_.documented(),
+            crate_.total(),
+            crate_.percent()
+        )?;
+        for module in &crate_.modules {
+            writeln!(
+                out,
+                "  {}: {}/{} ({:.1}%)",
+                module.path,
+                module.documented(),
+                module.total(),
+                module.percent()
+            )?;
+            for item in module.items.iter().filter(|item| !item.documented) {
+                writeln!(out, "    undocumented {}", item.label)?;
+            }
+        }
+    }
+    writeln!(
+        out,
+        "total: {}/{} documented ({:.1}%)",
+        crates.documented(),
+        crates.total(),
+        crates.percent()
+    )
+}
+
+/// The same as `write_text`, as JSON for other tools to check.
+pub fn to_json(crates: &[CrateCoverage]) -> serde_json::Value {
+    let crates_json = crates
+        .iter()
+        .map(|crate_| {
+            let modules = crate_
+                .modules
+                .iter()
+                .map(|module| {
+                    serde_json::json!({
+                        "path": module.path,
+                        "documented": module.documented(),
+                        "total": module.total(),
+                        "percent": module.percent(),
+                        "undocumented": module
+                            .items
+                            .iter()
+                            .filter(|item| !item.documented)
+                            .map(|item| &item.label)
+                            .collect::<Vec<_>>(),
+                    })
+                })
+                .collect::<Vec<_>>();
+            serde_json::json!({
+                "name": crate_.crate_name,
+                "documented": crate_.documented(),
+                "total": crate_.total(),
+                "percent": crate_.percent(),
+                "modules": modules,
+            })
+        })
+        .collect::<Vec<_>>();
+    serde_json::json!({
+        "documented": crates.documented(),
+        "total": crates.total(),
+        "percent": crates.percent(),
+        "crates": crates_json,
+    })
+}
@@ -0,0 +1,583 @@
+//! Comparing the public API of two versions of a crate.
+
+use crate::analysis::{self, Analysis, CrateId, Item, ItemId};
+use crate::browser_rustdoc::{
+    bounds_label, generic_label, generics_label, type_label, where_label,
+};
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+/// An item in a crate's public API.
+#[derive(Debug)]
+pub struct ApiItem<'a> {
+    pub id: ItemId<'a>,
+    pub item: &'a rustdoc_types::Item,
+    /// Text form of the item's declaration. Differences in this are what count as changes.
+    pub signature: String,
+}
+
+impl ApiItem<'_> {
+    /// Short name for what kind of item this is, used in labels, or `None` for the kinds that
+    /// aren't labeled with one.
+    pub fn kind_name(&self) -> Option<&'static str> {
+        use rustdoc_types::ItemEnum::*;
+        match &self.item.inner {
+            Impl(_) | Use(_) | ExternType | Primitive(_) => None,
+            _ => Some(analysis::kind_name(self.item)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl ChangeKind {
+    pub fn marker(self) -> char {
+        match self {
+            ChangeKind::Added => '+',
+            ChangeKind::Removed => '-',
+            ChangeKind::Changed => '~',
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Change<'a> {
+    /// The path the item is publicly reachable by, starting with the crate name. The last
+    /// component of trait impls is the impl, e.g. `["mycrate", "S", "impl Clone"]`.
+    pub path: Vec<String>,
+    pub kind: ChangeKind,
+    pub old: Option<ApiItem<'a>>,
+    pub new: Option<ApiItem<'a>>,
+}
+
+/// Compare the public API of all the workspace crates in either of the given analyses.
+pub fn diff<'a>(old: &'a Analysis, new: &'a Analysis) -> Vec<Change<'a>> {
+    let mut names = old
+        .crate_ids()
+        .chain(new.crate_ids())
+        .map(|id| id.crate_name().to_owned())
+        .filter(|name| old.is_workspace_crate(name) || new.is_workspace_crate(name))
+        .collect::<Vec<_>>();
+    names.sort();
+    names.dedup();
+    names
+        .iter()
+        .flat_map(|name| diff_crate(old, new, name))
+        .collect()
+}
+
+/// Compare the public API of one crate between the given analyses.
+pub fn diff_crate<'a>(old: &'a Analysis, new: &'a Analysis, crate_name: &str) -> Vec<Change<'a>> {
+    let mut old_api = public_api(old, crate_name);
+    let new_api = public_api(new, crate_name);
+    let mut changes = vec![];
+    for (path, new_item) in new_api {
+        match old_api.remove(&path) {
+            Some(old_item) => {
+                if old_item.signature != new_item.signature {
+                    changes.push(Change {
+                        path,
+                        kind: ChangeKind::Changed,
+                        old: Some(old_item),
+                        new: Some(new_item),
+                    });
+                }
+            }
+            None => changes.push(Change {
+                path,
+                kind: ChangeKind::Added,
+                old: None,
+                new: Some(new_item),
+            }),
+        }
+    }
+    changes.extend(old_api.into_iter().map(|(path, old_item)| Change {
+        path,
+        kind: ChangeKind::Removed,
+        old: Some(old_item),
+        new: None,
+    }));
+    changes.sort_by(|a, b| a.path.cmp(&b.path));
+    changes
+}
+
+/// Print the changes, one per line, with their signatures indented below.
+pub fn write_text(changes: &[Change], out: &mut impl Write) -> io::Result<()> {
+    for change in changes {
+        writeln!(out, "{} {}", change.kind.marker(), change.path.join("::"))?;
+        if let Some(old) = &change.old {
+            writeln!(out, "    - {}", old.signature)?;
+        }
+        if let Some(new) = &change.new {
+            writeln!(out, "    + {}", new.signature)?;
+        }
+    }
+    Ok(())
+}
+
+/// Find all the items reachable through public paths in a crate, keyed by those paths.
+pub fn public_api<'a>(
+    analysis: &'a Analysis,
+    crate_name: &str,
+) -> BTreeMap<Vec<String>, ApiItem<'a>> {
+    let mut walker = ApiWalker {
+        analysis,
+        items: BTreeMap::new(),
+        visited: vec![],
+    };
+    if let Some((name, _)) = analysis.crates.get_key_value(crate_name) {
+        let root_id = ItemId::crate_root(CrateId { name });
+        if let Some((root_id, Item::Item(root))) = analysis.get_item(root_id) {
+            if let rustdoc_types::ItemEnum::Module(m) = &root.inner {
+                walker.walk_module(&root_id, m, std::slice::from_ref(name));
+            }
+        }
+    }
+    walker.items
+}
+
+struct ApiWalker<'a> {
+    analysis: &'a Analysis,
+    items: BTreeMap<Vec<String>, ApiItem<'a>>,
+    /// Modules currently being walked, to avoid following cyclic re-exports forever.
+    visited: Vec<ItemId<'a>>,
+}
+
+impl<'a> ApiWalker<'a> {
+    /// Look up items by their IDs in the crate of `sibling_of`, skipping any that can't be found.
+    fn get_all(
+        &self,
+        sibling_of: &ItemId<'a>,
+        ids: impl IntoIterator<Item = &'a rustdoc_types::Id>,
+    ) -> Vec<(ItemId<'a>, &'a rustdoc_types::Item)> {
+        ids.into_iter()
+            .filter_map(
+                |id| match self.analysis.get_item(sibling_of.crate_sibling(id))? {
+                    (id, Item::Item(item)) => Some((id, item)),
+                    (_, Item::Root) => None,
+                },
+            )
+            .collect()
+    }
+
+    fn walk_module(
+        &mut self,
+        module_id: &ItemId<'a>,
+        module: &'a rustdoc_types::Module,
+        path: &[String],
+    ) {
+        if self.visited.contains(module_id) {
+            return;
+        }
+        self.visited.push(module_id.clone());
+
+        for (id, item) in self.get_all(module_id, &module.items) {
+            if !analysis::is_public(item, None) || analysis::is_doc_hidden(item) {
+                continue;
+            }
+            let rustdoc_types::ItemEnum::Use(u) = &item.inner else {
+                if let Some(name) = &item.name {
+                    self.add(child_path(path, name), id, item);
+                }
+                continue;
+            };
+            let Some((target_id, target)) = self.analysis.resolve_use(&id, item) else {
+                continue;
+            };
+            if !u.is_glob {
+                self.add(child_path(path, &u.name), target_id, target);
+                continue;
+            }
+            match &target.inner {
+                rustdoc_types::ItemEnum::Module(m) => self.walk_module(&target_id, m, path),
+                rustdoc_types::ItemEnum::Enum(e) => {
+                    for (variant_id, variant) in self.get_all(&target_id, &e.variants) {
+                        if let Some(name) = &variant.name {
+                            self.insert(child_path(path, name), variant_id, variant);
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        self.visited.pop();
+    }
+
+    fn insert(&mut self, path: Vec<String>, id: ItemId<'a>, item: &'a rustdoc_types::Item) {
+        let signature = self.signature(&id, item);
+        self.items.insert(
+            path,
+            ApiItem {
+                id,
+                item,
+                signature,
+            },
+        );
+    }
+
+    /// Add an item and its public members.
+    fn add(&mut self, path: Vec<String>, id: ItemId<'a>, item: &'a rustdoc_types::Item) {
+        use rustdoc_types::ItemEnum::*;
+        match &item.inner {
+            Module(m) => {
+                self.insert(path.clone(), id.clone(), item);
+                self.walk_module(&id, m, &path);
+            }
+            Struct(s) => {
+                let fields: Vec<&rustdoc_types::Id> = match &s.kind {
+                    rustdoc_types::StructKind::Unit => vec![],
+                    rustdoc_types::StructKind::Tuple(t) => t.iter().flatten().collect(),
+                    rustdoc_types::StructKind::Plain { fields, .. } => fields.iter().collect(),
+                };
+                self.add_fields(&path, &id, fields);
+                self.add_impls(&path, &id, &s.impls);
+                self.insert(path, id, item);
+            }
+            Union(u) => {
+                self.add_fields(&path, &id, u.fields.iter().collect());
+                self.add_impls(&path, &id, &u.impls);
+                self.insert(path, id, item);
+            }
+            Enum(e) => {
+                for (variant_id, variant) in self.get_all(&id, &e.variants) {
+                    if let Some(name) = &variant.name {
+                        self.insert(child_path(&path, name), variant_id, variant);
+                    }
+                }
+                self.add_impls(&path, &id, &e.impls);
+                self.insert(path, id, item);
+            }
+            Trait(t) => {
+                for (member_id, member) in self.get_all(&id, &t.items) {
+                    if let Some(name) = &member.name {
+                        self.insert(child_path(&path, name), member_id, member);
+                    }
+                }
+                self.insert(path, id, item);
+            }
+            Function(_)
+            | Constant { .. }
+            | Static(_)
+            | TypeAlias(_)
+            | TraitAlias(_)
+            | Macro(_)
+            | ProcMacro(_)
+            | ExternCrate { .. }
+            | ExternType => {
+                self.insert(path, id, item);
+            }
+            Use(_)
+            | Impl(_)
+            | StructField(_)
+            | Variant(_)
+            | Primitive(_)
+            | AssocConst { .. }
+            | AssocType { .. } => (),
+        }
+    }
+
+    fn add_fields(
+        &mut self,
+        path: &[String],
+        parent_id: &ItemId<'a>,
+        fields: Vec<&'a rustdoc_types::Id>,
+    ) {
+        for (field_id, field) in self.get_all(parent_id, fields) {
+            match &field.name {
+                Some(name)
+                    if analysis::is_public(field, None) && !analysis::is_doc_hidden(field) =>
+                {
+                    self.insert(child_path(path, name), field_id, field)
+                }
+                _ => (),
+            }
+        }
+    }
+
+    fn add_impls(&mut self, path: &[String], type_id: &ItemId<'a>, impls: &'a [rustdoc_types::Id]) {
+        for (impl_id, impl_item) in self.get_all(type_id, impls) {
+            let rustdoc_types::ItemEnum::Impl(i) = &impl_item.inner else {
+                continue;
+            };
+            if i.blanket_impl.is_some() || analysis::is_doc_hidden(impl_item) {
+                // Blanket impls follow from other impls, so don't need to be compared.
+                continue;
+            }
+            match &i.trait_ {
+                Some(trait_) => {
+                    let name = format!(
+                        "impl {}{}",
+                        if i.is_negative { "!" } else { "" },
+                        self.trait_name(&impl_id, trait_)
+                    );
+                    self.insert(child_path(path, &name), impl_id, impl_item);
+                }
+                None => {
+                    for (member_id, member) in self.get_all(&impl_id, &i.items) {
+                        match &member.name {
+                            Some(name)
+                                if analysis::is_public(member, Some(impl_item))
+                                    && !analysis::is_doc_hidden(member) =>
+                            {
+                                self.insert(child_path(path, name), member_id, member)
+                            }
+                            _ => (),
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// The full path of a trait, or the path as written if it can't be found.
+    fn trait_name(&self, id: &ItemId<'a>, trait_: &'a rustdoc_types::Path) -> String {
+        let mut name = match self
+            .analysis
+            .get_path(id.crate_sibling(&trait_.id), &trait_.path)
+        {
+            Some(path) => path.join("::"),
+            None => trait_.path.clone(),
+        };
+        if let Some(args) = &trait_.args {
+            name.push_str(&generic_label(args));
+        }
+        name
+    }
+
+    fn signature(&self, id: &ItemId<'a>, item: &'a rustdoc_types::Item) -> String {
+        use rustdoc_types::ItemEnum::*;
+        let name = item.name.as_deref().unwrap_or("_");
+        let mut sig = String::new();
+        for attr in &item.attrs {
+            if attr == "#[non_exhaustive]" {
+                sig.push_str("#[non_exhaustive] ");
+            }
+        }
+        if item.visibility == rustdoc_types::Visibility::Public {
+            sig.push_str("pub ");
+        }
+        match &item.inner {
+            Module(_) => sig += &format!("mod {name}"),
+            ExternCrate { name, rename } => {
+                sig += &format!("extern crate {name}");
+                if let Some(rename) = rename {
+                    sig += &format!(" as {rename}");
+                }
+            }
+            Struct(s) => {
+                sig += &format!("struct {name}{}", generics_label(&s.generics));
+                let fields: Vec<&rustdoc_types::Id> = match &s.kind {
+                    rustdoc_types::StructKind::Unit => {
+                        sig += &where_label(&s.generics);
+                        sig.push(';');
+                        return sig;
+                    }
+                    rustdoc_types::StructKind::Tuple(t) => {
+                        if t.iter().any(Option::is_none) {
+                            sig += "(/* private fields */)";
+                        } else {
+                            sig += "(..)";
+                        }
+                        t.iter().flatten().collect()
+                    }
+                    rustdoc_types::StructKind::Plain { fields, .. } => {
+                        sig += " { .. }";
+                        fields.iter().collect()
+                    }
+                };
+                sig += &where_label(&s.generics);
+                // Whether there are private fields matters, as it prevents constructing and
+                // destructuring the struct.
+                if self
+                    .get_all(id, fields)
+                    .iter()
+                    .any(|(_, f)| !analysis::is_public(f, None) || analysis::is_doc_hidden(f))
+                {
+                    sig += " /* private fields */";
+                }
+            }
+            Union(u) => {
+                sig += &format!(
+                    "union {name}{}{}",
+                    generics_label(&u.generics),
+                    where_label(&u.generics)
+                );
+            }
+            StructField(ty) => sig += &format!("{name}: {}", type_label(ty)),
+            Enum(e) => {
+                sig += &format!(
+                    "enum {name}{}{}",
+                    generics_label(&e.generics),
+                    where_label(&e.generics)
+                );
+            }
+            Variant(v) => {
+                sig.push_str(name);
+                let fields = |ids: Vec<&'a rustdoc_types::Id>| {
+                    self.get_all(id, ids)
+                        .into_iter()
+                        .map(|(_, f)| match &f.inner {
+                            StructField(ty) => (f.name.as_deref().unwrap_or("_"), type_label(ty)),
+                            _ => ("_", String::new()),
+                        })
+                        .collect::<Vec<_>>()
+                };
+                match &v.kind {
+                    rustdoc_types::VariantKind::Plain => (),
+                    rustdoc_types::VariantKind::Tuple(t) => {
+                        let types = fields(t.iter().flatten().collect())
+                            .into_iter()
+                            .map(|(_, ty)| ty)
+                            .collect::<Vec<_>>();
+                        sig += &format!("({})", types.join(", "));
+                    }
+                    rustdoc_types::VariantKind::Struct { fields: ids, .. } => {
+                        let fields = fields(ids.iter().collect())
+                            .into_iter()
+                            .map(|(name, ty)| format!("{name}: {ty}"))
+                            .collect::<Vec<_>>();
+                        sig += &format!(" {{ {} }}", fields.join(", "));
+                    }
+                }
+                if let Some(d) = &v.discriminant {
+                    sig += &format!(" = {}", d.value);
+                }
+            }
+            Function(f) => {
+                sig += &fn_signature(name, f);
+                if !f.has_body {
+                    // Required trait method.
+                    sig.push(';');
+                }
+            }
+            Trait(t) => {
+                if t.is_auto {
+                    sig += "auto ";
+                }
+                if t.is_unsafe {
+                    sig += "unsafe ";
+                }
+                sig += &format!("trait {name}{}", generics_label(&t.generics));
+                if !t.bounds.is_empty() {
+                    sig += &format!(": {}", bounds_label(&t.bounds));
+                }
+                sig += &where_label(&t.generics);
+            }
+            TraitAlias(t) => {
+                sig += &format!(
+                    "trait {name}{} = {}{}",
+                    generics_label(&t.generics),
+                    bounds_label(&t.params),
+                    where_label(&t.generics),
+                );
+            }
+            Impl(i) => {
+                if i.is_unsafe {
+                    sig += "unsafe ";
+                }
+                sig += &format!("impl{} ", generics_label(&i.generics));
+                if let Some(trait_) = &i.trait_ {
+                    if i.is_negative {
+                        sig.push('!');
+                    }
+                    sig += &format!("{} for ", self.trait_name(id, trait_));
+                }
+                sig += &type_label(&i.for_);
+                sig += &where_label(&i.generics);
+            }
+            TypeAlias(t) => {
+                sig += &format!(
+                    "type {name}{}{} = {}",
+                    generics_label(&t.generics),
+                    where_label(&t.generics),
+                    type_label(&t.type_),
+                );
+            }
+            Constant { type_, .. } => sig += &format!("const {name}: {}", type_label(type_)),
+            Static(s) => {
+                sig += &format!(
+                    "static {}{name}: {}",
+                    if s.is_mutable { "mut " } else { "" },
+                    type_label(&s.type_),
+                );
+            }
+            ExternType => sig += &format!("type {name}"),
+            Macro(_) => sig += &format!("macro_rules! {name}"),
+            ProcMacro(p) => {
+                sig += &match p.kind {
+                    rustdoc_types::MacroKind::Bang => format!("macro {name}!"),
+                    rustdoc_types::MacroKind::Attr => format!("#[{name}]"),
+                    rustdoc_types::MacroKind::Derive => format!("#[derive({name})]"),
+                };
+            }
+            AssocConst { type_, .. } => sig += &format!("const {name}: {}", type_label(type_)),
+            AssocType {
+                generics,
+                bounds,
+                type_,
+            } => {
+                sig += &format!("type {name}{}", generics_label(generics));
+                if !bounds.is_empty() {
+                    sig += &format!(": {}", bounds_label(bounds));
+                }
+                sig += &where_label(generics);
+                if let Some(ty) = type_ {
+                    sig += &format!(" = {}", type_label(ty));
+                }
+            }
+            Use(_) | Primitive(_) => sig.push_str(name),
+        }
+        sig
+    }
+}
+
+/// The signature of a function, not including its visibility.
+pub fn fn_signature(name: &str, f: &rustdoc_types::Function) -> String {
+    let mut sig = String::new();
+    if f.header.is_const {
+        sig += "const ";
+    }
+    if f.header.is_async {
+        sig += "async ";
+    }
+    if f.header.is_unsafe {
+        sig += "unsafe ";
+    }
+    match &f.header.abi {
+        rustdoc_types::Abi::Rust => (),
+        rustdoc_types::Abi::C { .. } => sig += "extern \"C\" ",
+        rustdoc_types::Abi::System { .. } => sig += "extern \"system\" ",
+        rustdoc_types::Abi::Cdecl { .. } => sig += "extern \"cdecl\" ",
+        rustdoc_types::Abi::Stdcall { .. } => sig += "extern \"stdcall\" ",
+        rustdoc_types::Abi::Fastcall { .. } => sig += "extern \"fastcall\" ",
+        rustdoc_types::Abi::Aapcs { .. } => sig += "extern \"aapcs\" ",
+        rustdoc_types::Abi::Win64 { .. } => sig += "extern \"win64\" ",
+        rustdoc_types::Abi::SysV64 { .. } => sig += "extern \"sysv64\" ",
+        rustdoc_types::Abi::Other(abi) => sig += &format!("extern \"{abi}\" "),
+    }
+    let args = f
+        .sig
+        .inputs
+        .iter()
+        .map(|(name, ty)| format!("{name}: {}", type_label(ty)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    sig += &format!("fn {name}{}({args})", generics_label(&f.generics));
+    if let Some(ty) = &f.sig.output {
+        sig += &format!(" -> {}", type_label(ty));
+    }
+    sig += &where_label(&f.generics);
+    sig
+}
+
+fn child_path(path: &[String], name: &str) -> Vec<String> {
+    let mut path = path.to_vec();
+    path.push(name.to_owned());
+    path
+}
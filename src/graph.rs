@@ -0,0 +1,407 @@
+//! Graphs of how items relate to each other, for exporting as Graphviz DOT or Mermaid.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io::{self, Write};
+
+use crate::analysis::{self, Analysis, Item, ItemId};
+
+#[derive(Debug, Default)]
+pub struct Graph {
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+    /// Node indices by name.
+    index: HashMap<String, usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Node {
+    /// Unique name, e.g. the item's full path.
+    pub name: String,
+    pub kind: NodeKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeKind {
+    Struct,
+    Enum,
+    Union,
+    Trait,
+    TypeAlias,
+    Primitive,
+    Crate,
+    /// Something whose definition isn't in the loaded analysis data.
+    Unresolved,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Edge {
+    pub from: usize,
+    pub to: usize,
+    pub label: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Dot,
+    Mermaid,
+}
+
+impl Format {
+    pub fn name(self) -> &'static str {
+        match self {
+            Format::Dot => "DOT",
+            Format::Mermaid => "Mermaid",
+        }
+    }
+
+    /// Conventional file extension for the format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Dot => "dot",
+            Format::Mermaid => "mmd",
+        }
+    }
+}
+
+impl NodeKind {
+    fn name(self) -> &'static str {
+        match self {
+            NodeKind::Struct => "struct",
+            NodeKind::Enum => "enum",
+            NodeKind::Union => "union",
+            NodeKind::Trait => "trait",
+            NodeKind::TypeAlias => "type",
+            NodeKind::Primitive => "primitive",
+            NodeKind::Crate | NodeKind::Unresolved => "",
+        }
+    }
+}
+
+impl Node {
+    pub fn label(&self) -> String {
+        match self.kind.name() {
+            "" => self.name.clone(),
+            kind => format!("{kind} {}", self.name),
+        }
+    }
+}
+
+impl Graph {
+    /// Get the index of the named node, adding it if it isn't there yet.
+    pub fn add_node(&mut self, name: String, kind: NodeKind) -> usize {
+        if let Some(&idx) = self.index.get(&name) {
+            return idx;
+        }
+        let idx = self.nodes.len();
+        self.index.insert(name.clone(), idx);
+        self.nodes.push(Node { name, kind });
+        idx
+    }
+
+    /// Add an edge, unless the same one is already there.
+    pub fn add_edge(&mut self, from: usize, to: usize, label: impl Into<String>) {
+        let edge = Edge {
+            from,
+            to,
+            label: label.into(),
+        };
+        if !self.edges.contains(&edge) {
+            self.edges.push(edge);
+        }
+    }
+
+    /// Get an edge's endpoints' names and its label.
+    pub fn edge_names<'g>(&'g self, edge: &'g Edge) -> (&'g str, &'g str, &'g str) {
+        (
+            &self.nodes[edge.from].name,
+            &self.nodes[edge.to].name,
+            &edge.label,
+        )
+    }
+
+    pub fn write(&self, format: Format, out: &mut impl Write) -> io::Result<()> {
+        match format {
+            Format::Dot => self.write_dot(out),
+            Format::Mermaid => self.write_mermaid(out),
+        }
+    }
+
+    pub fn write_dot(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "digraph {{")?;
+        writeln!(out, "    rankdir=LR;")?;
+        writeln!(out, "    node [shape=box];")?;
+        for (i, node) in self.nodes.iter().enumerate() {
+            let style = match node.kind {
+                NodeKind::Trait => ", shape=ellipse",
+                NodeKind::Unresolved => ", style=dashed",
+                _ => "",
+            };
+            writeln!(out, "    n{i} [label={}{style}];", dot_quote(&node.label()))?;
+        }
+        for edge in &self.edges {
+            if edge.label.is_empty() {
+                writeln!(out, "    n{} -> n{};", edge.from, edge.to)?;
+            } else {
+                writeln!(
+                    out,
+                    "    n{} -> n{} [label={}];",
+                    edge.from,
+                    edge.to,
+                    dot_quote(&edge.label)
+                )?;
+            }
+        }
+        writeln!(out, "}}")
+    }
+
+    pub fn write_mermaid(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "graph LR")?;
+        for (i, node) in self.nodes.iter().enumerate() {
+            let label = mermaid_quote(&node.label());
+            match node.kind {
+                NodeKind::Trait => writeln!(out, "    n{i}([{label}])")?,
+                _ => writeln!(out, "    n{i}[{label}]")?,
+            }
+        }
+        for edge in &self.edges {
+            if edge.label.is_empty() {
+                writeln!(out, "    n{} --> n{}", edge.from, edge.to)?;
+            } else {
+                writeln!(
+                    out,
+                    "    n{} -->|{}| n{}",
+                    edge.from,
+                    mermaid_quote(&edge.label),
+                    edge.to
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn dot_quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn mermaid_quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('"', "#quot;"))
+}
+
+/// Build a graph of which of the loaded crates depend on which others.
+pub fn crate_graph(analysis: &Analysis) -> Graph {
+    let mut graph = Graph::default();
+    let mut names = analysis.crates.keys().collect::<Vec<_>>();
+    names.sort();
+    for name in names {
+        let from = graph.add_node(name.clone(), NodeKind::Crate);
+        for dep in analysis.crate_dependencies(name) {
+            let to = graph.add_node(dep.clone(), NodeKind::Crate);
+            graph.add_edge(from, to, "");
+        }
+    }
+    graph
+}
+
+/// Build a graph of the types reachable from the given item, following the types of fields and
+/// enum variants, the traits types implement, and what type aliases refer to, up to `depth` steps
+/// away.
+pub fn type_graph<'a>(analysis: &'a Analysis, root: ItemId<'a>, depth: usize) -> Graph {
+    let mut graph = Graph::default();
+    let Some((root_id, Item::Item(root_item))) = analysis.get_item(root) else {
+        return graph;
+    };
+    let Some(root_idx) = add_item_node(&mut graph, analysis, &root_id, root_item) else {
+        return graph;
+    };
+
+    let mut expanded = HashSet::new();
+    let mut queue = VecDeque::from([(root_id, root_item, root_idx, 0)]);
+    while let Some((id, item, idx, level)) = queue.pop_front() {
+        if level >= depth || !expanded.insert(idx) {
+            continue;
+        }
+
+        for (label, target_id) in type_edges(analysis, &id, item) {
+            let Some(path) = analysis.get_path(target_id.clone(), "graph") else {
+                continue;
+            };
+            match analysis.get_item(target_id) {
+                Some((target_id, Item::Item(target))) => {
+                    let Some(target_idx) = add_item_node(&mut graph, analysis, &target_id, target)
+                    else {
+                        continue;
+                    };
+                    graph.add_edge(idx, target_idx, label);
+                    queue.push_back((target_id, target, target_idx, level + 1));
+                }
+                _ => {
+                    let target_idx = graph.add_node(path.join("::"), NodeKind::Unresolved);
+                    graph.add_edge(idx, target_idx, label);
+                }
+            }
+        }
+    }
+    graph
+}
+
+/// Add a node for a type, trait, or type alias, following imports to what they refer to.
+fn add_item_node<'a>(
+    graph: &mut Graph,
+    analysis: &'a Analysis,
+    id: &ItemId<'a>,
+    item: &'a rustdoc_types::Item,
+) -> Option<usize> {
+    use rustdoc_types::ItemEnum;
+    if let Some((target_id, target)) = analysis.resolve_use(id, item) {
+        return add_item_node(graph, analysis, &target_id, target);
+    }
+    let kind = match &item.inner {
+        ItemEnum::Struct(_) => NodeKind::Struct,
+        ItemEnum::Enum(_) => NodeKind::Enum,
+        ItemEnum::Union(_) => NodeKind::Union,
+        ItemEnum::Trait(_) => NodeKind::Trait,
+        ItemEnum::TypeAlias(_) => NodeKind::TypeAlias,
+        ItemEnum::Primitive(_) => NodeKind::Primitive,
+        _ => return None,
+    };
+    let name = match analysis.get_path(id.clone(), "graph") {
+        Some(path) => path.join("::"),
+        None => item.name.clone()?,
+    };
+    Some(graph.add_node(name, kind))
+}
+
+/// The items a type refers to, with a label for how.
+fn type_edges<'a>(
+    analysis: &'a Analysis,
+    id: &ItemId<'a>,
+    item: &'a rustdoc_types::Item,
+) -> Vec<(String, ItemId<'a>)> {
+    use rustdoc_types::ItemEnum;
+    if let Some((target_id, target)) = analysis.resolve_use(id, item) {
+        return type_edges(analysis, &target_id, target);
+    }
+    let child = |child_id: &'a rustdoc_types::Id| {
+        analysis
+            .get_item(id.crate_sibling(child_id))
+            .and_then(|(child_id, child)| match child {
+                Item::Item(child) => Some((child_id, child)),
+                Item::Root => None,
+            })
+    };
+    let field_edges = |fields: Vec<&'a rustdoc_types::Id>, label: &dyn Fn(&str) -> String| {
+        fields
+            .into_iter()
+            .filter_map(child)
+            .flat_map(|(field_id, field)| {
+                let ItemEnum::StructField(ty) = &field.inner else {
+                    return vec![];
+                };
+                let label = label(field.name.as_deref().unwrap_or("_"));
+                referenced_type_ids(ty)
+                    .into_iter()
+                    .map(|ty_id| (label.clone(), field_id.crate_sibling(ty_id)))
+                    .collect()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut edges = vec![];
+    let impls = match &item.inner {
+        ItemEnum::Struct(s) => {
+            let fields = match &s.kind {
+                rustdoc_types::StructKind::Unit => vec![],
+                rustdoc_types::StructKind::Tuple(t) => t.iter().flatten().collect(),
+                rustdoc_types::StructKind::Plain { fields, .. } => fields.iter().collect(),
+            };
+            edges.extend(field_edges(fields, &|name| format!("field {name}")));
+            &s.impls
+        }
+        ItemEnum::Union(u) => {
+            edges.extend(field_edges(u.fields.iter().collect(), &|name| {
+                format!("field {name}")
+            }));
+            &u.impls
+        }
+        ItemEnum::Enum(e) => {
+            for (_, variant) in e.variants.iter().filter_map(child) {
+                let ItemEnum::Variant(v) = &variant.inner else {
+                    continue;
+                };
+                let fields = match &v.kind {
+                    rustdoc_types::VariantKind::Plain => vec![],
+                    rustdoc_types::VariantKind::Tuple(t) => t.iter().flatten().collect(),
+                    rustdoc_types::VariantKind::Struct { fields, .. } => fields.iter().collect(),
+                };
+                let name = variant.name.as_deref().unwrap_or("_");
+                edges.extend(field_edges(fields, &|_| format!("variant {name}")));
+            }
+            &e.impls
+        }
+        ItemEnum::Primitive(p) => &p.impls,
+        ItemEnum::TypeAlias(alias) => {
+            return referenced_type_ids(&alias.type_)
+                .into_iter()
+                .map(|ty_id| ("alias of".to_owned(), id.crate_sibling(ty_id)))
+                .collect();
+        }
+        _ => return edges,
+    };
+
+    // Auto trait and blanket impls are left out, because nearly every type has the same ones.
+    for (impl_id, impl_item) in impls.iter().filter_map(child) {
+        if let ItemEnum::Impl(i) = &impl_item.inner {
+            if let (Some(trait_), false, None) = (&i.trait_, i.is_synthetic, &i.blanket_impl) {
+                edges.push(("implements".to_owned(), impl_id.crate_sibling(&trait_.id)));
+            }
+        }
+    }
+    edges
+}
+
+/// IDs of the types a type refers to, including in generic arguments (so `Vec<Foo>` refers to both
+/// `Vec` and `Foo`).
+fn referenced_type_ids(ty: &rustdoc_types::Type) -> Vec<&rustdoc_types::Id> {
+    use rustdoc_types::Type;
+    let mut ids = analysis::type_ids(ty);
+    let args = match ty {
+        Type::ResolvedPath(path) => path.args.as_deref(),
+        Type::DynTrait(dt) => {
+            for t in &dt.traits {
+                ids.extend(generic_args_type_ids(t.trait_.args.as_deref()));
+            }
+            None
+        }
+        Type::Tuple(types) => {
+            ids = types.iter().flat_map(referenced_type_ids).collect();
+            None
+        }
+        Type::Slice(ty)
+        | Type::Array { type_: ty, .. }
+        | Type::RawPointer { type_: ty, .. }
+        | Type::BorrowedRef { type_: ty, .. } => return referenced_type_ids(ty),
+        _ => None,
+    };
+    ids.extend(generic_args_type_ids(args));
+    ids
+}
+
+fn generic_args_type_ids(args: Option<&rustdoc_types::GenericArgs>) -> Vec<&rustdoc_types::Id> {
+    use rustdoc_types::{GenericArg, GenericArgs};
+    match args {
+        Some(GenericArgs::AngleBracketed { args, .. }) => args
+            .iter()
+            .flat_map(|arg| match arg {
+                GenericArg::Type(ty) => referenced_type_ids(ty),
+                _ => vec![],
+            })
+            .collect(),
+        Some(GenericArgs::Parenthesized { inputs, output }) => inputs
+            .iter()
+            .chain(output)
+            .flat_map(referenced_type_ids)
+            .collect(),
+        _ => vec![],
+    }
+}
@@ -0,0 +1,256 @@
+//! Answering whether a type implements a trait, and by which impls.
+
+use std::collections::BTreeMap;
+
+use anyhow::bail;
+
+use crate::analysis::{self, Analysis, CrateId, Item, ItemId};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ImplKind {
+    /// An impl of the trait written for the type.
+    Direct,
+    /// An impl for all types meeting some bounds, which rustdoc found could apply to the type.
+    Blanket,
+    /// An auto trait impl the compiler derived from the type's fields.
+    Auto,
+}
+
+impl ImplKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            ImplKind::Direct => "direct impl",
+            ImplKind::Blanket => "blanket impl",
+            ImplKind::Auto => "auto trait impl",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ImplMatch<'a> {
+    pub kind: ImplKind,
+    pub id: ItemId<'a>,
+    pub item: &'a rustdoc_types::Item,
+    pub impl_: &'a rustdoc_types::Impl,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Answer {
+    Yes,
+    /// Only by blanket impls, whose bounds the type may or may not meet.
+    IfBoundsHold,
+    /// The type explicitly doesn't implement the trait, with a negative impl, or the compiler
+    /// found it doesn't implement an auto trait.
+    No,
+    /// No impls were found in the loaded crates.
+    NotFound,
+}
+
+impl Answer {
+    /// Work out the answer from the impls found.
+    pub fn from_impls(impls: &[ImplMatch]) -> Self {
+        if impls.iter().any(|m| m.impl_.is_negative) {
+            Answer::No
+        } else if impls.iter().any(|m| m.kind != ImplKind::Blanket) {
+            Answer::Yes
+        } else if !impls.is_empty() {
+            Answer::IfBoundsHold
+        } else {
+            Answer::NotFound
+        }
+    }
+
+    /// A sentence answering whether the type implements the trait.
+    pub fn describe(self, type_name: &str, trait_name: &str) -> String {
+        match self {
+            Answer::Yes => format!("{type_name} implements {trait_name}"),
+            Answer::IfBoundsHold => format!(
+                "{type_name} implements {trait_name} by a blanket impl, if it meets the impl's \
+                 bounds"
+            ),
+            Answer::No => format!("{type_name} does not implement {trait_name}"),
+            Answer::NotFound => format!("no impl of {trait_name} for {type_name} was found"),
+        }
+    }
+}
+
+/// Find a struct, enum, union, or primitive type (if `trait_` is false) or a trait (if it's true)
+/// in any loaded crate by its full path, or the end of it, like `Vec` or `collections::HashMap`.
+/// Primitive types can also be written like `&T` or `[T]`. It's an error if there's none or more
+/// than one.
+pub fn resolve<'a>(analysis: &'a Analysis, path: &str, trait_: bool) -> anyhow::Result<ItemId<'a>> {
+    use rustdoc_types::ItemKind;
+    let what = if trait_ { "trait" } else { "type" };
+    let primitive = (!trait_).then(|| primitive_name(path.trim())).flatten();
+    let segments = match primitive {
+        Some(name) => vec![name],
+        None => path.trim().split("::").map(str::trim).collect::<Vec<_>>(),
+    };
+    if segments.iter().any(|s| s.is_empty()) {
+        bail!("{path:?} isn't a path");
+    }
+    // Crates' paths include the external items they use, so the same path can be in several.
+    // Prefer the crate defining it, which has its details.
+    let mut found = BTreeMap::<String, (bool, ItemId)>::new();
+    for (name, crate_) in &analysis.crates {
+        for (id, summary) in &crate_.paths {
+            let kind_matches = if trait_ {
+                summary.kind == ItemKind::Trait
+            } else {
+                matches!(
+                    summary.kind,
+                    ItemKind::Struct | ItemKind::Enum | ItemKind::Union | ItemKind::Primitive
+                )
+            };
+            if !kind_matches || !ends_with(&summary.path, &segments) {
+                continue;
+            }
+            let local = summary.crate_id == 0;
+            let id = ItemId::crate_root(CrateId { name }).crate_sibling(id);
+            // Primitive types are documented in several crates (`core::u8` and `std::u8`), but are
+            // the same type.
+            let key = match summary.kind {
+                ItemKind::Primitive => summary.path.last().cloned().unwrap_or_default(),
+                _ => summary.path.join("::"),
+            };
+            let entry = found.entry(key).or_insert((local, id.clone()));
+            if local && !entry.0 {
+                *entry = (local, id);
+            }
+        }
+    }
+    match found.len() {
+        0 => bail!("no {what} named {path:?} was found"),
+        1 => Ok(found.into_values().next().unwrap().1),
+        _ => {
+            let paths = found.into_keys().collect::<Vec<_>>();
+            bail!("{path:?} could be any of: {}", paths.join(", "))
+        }
+    }
+}
+
+/// The name rustdoc documents a primitive type under, for ones written the way they're used, like
+/// `&T` for `reference` or `[T]` for `slice`.
+fn primitive_name(ty: &str) -> Option<&str> {
+    Some(match ty {
+        _ if ty.starts_with('&') => "reference",
+        _ if ty.starts_with('*') => "pointer",
+        _ if ty.starts_with("fn(") => "fn",
+        "()" => "unit",
+        _ if ty.starts_with('(') => "tuple",
+        _ if ty.starts_with('[') && ty.contains(';') => "array",
+        _ if ty.starts_with('[') => "slice",
+        _ => return None,
+    })
+}
+
+/// Whether a full path ends with the given segments.
+fn ends_with(path: &[String], segments: &[&str]) -> bool {
+    path.len() >= segments.len()
+        && path[path.len() - segments.len()..]
+            .iter()
+            .zip(segments)
+            .all(|(a, b)| a == b)
+}
+
+/// Find the impls of a trait for a type: ones written for it in any loaded crate, the blanket and
+/// auto trait impls rustdoc listed under it, and blanket impls in other crates.
+pub fn find_impls<'a>(
+    analysis: &'a Analysis,
+    type_id: &ItemId<'a>,
+    trait_id: &ItemId<'a>,
+) -> Vec<ImplMatch<'a>> {
+    let (Some(type_path), Some(trait_path)) = (
+        analysis.get_path(type_id.clone(), "impl query type"),
+        analysis.get_path(trait_id.clone(), "impl query trait"),
+    ) else {
+        return vec![];
+    };
+    let is_trait = |impl_id: &ItemId, i: &rustdoc_types::Impl| {
+        i.trait_
+            .as_ref()
+            .is_some_and(|t| analysis.is_path_to(impl_id, t, trait_path))
+    };
+    // Primitive types are matched by name, as they don't have paths where they're used.
+    let primitive = (analysis.get_kind(type_id) == Some(rustdoc_types::ItemKind::Primitive))
+        .then(|| type_path.last().map(String::as_str))
+        .flatten();
+    let is_type = |impl_id: &ItemId, ty: &rustdoc_types::Type| match primitive {
+        Some(name) => analysis::primitive_name(ty) == Some(name),
+        None => matches!(ty, rustdoc_types::Type::ResolvedPath(p)
+            if analysis.is_path_to(impl_id, p, type_path)),
+    };
+
+    let mut matches = vec![];
+    // The type's own list has the impls rustdoc synthesized for it.
+    let (type_id, ty) = match analysis.get_item(type_id.clone()) {
+        Some((id, Item::Item(ty))) => (id, Some(ty)),
+        _ => (type_id.clone(), None),
+    };
+    let type_impls = match ty {
+        Some(ty) => match &ty.inner {
+            rustdoc_types::ItemEnum::Struct(s) => &s.impls[..],
+            rustdoc_types::ItemEnum::Enum(e) => &e.impls,
+            rustdoc_types::ItemEnum::Union(u) => &u.impls,
+            rustdoc_types::ItemEnum::Primitive(p) => &p.impls,
+            _ => &[],
+        },
+        _ => &[],
+    };
+    for id in type_impls {
+        if let Some((id, Item::Item(item))) = analysis.get_item(type_id.crate_sibling(id)) {
+            if let rustdoc_types::ItemEnum::Impl(i) = &item.inner {
+                if is_trait(&id, i) {
+                    let kind = if i.is_synthetic {
+                        ImplKind::Auto
+                    } else if i.blanket_impl.is_some() {
+                        ImplKind::Blanket
+                    } else {
+                        ImplKind::Direct
+                    };
+                    matches.push(ImplMatch {
+                        kind,
+                        id,
+                        item,
+                        impl_: i,
+                    });
+                }
+            }
+        }
+    }
+
+    // Impls in other crates, e.g. the trait's, aren't in that list. Neither are blanket impls
+    // from crates the type's crate doesn't use, but if rustdoc listed any, it found them all.
+    let listed_blanket = matches.iter().any(|m| m.kind == ImplKind::Blanket);
+    for (name, crate_) in &analysis.crates {
+        if ty.is_some() && name == type_id.crate_name() {
+            continue;
+        }
+        let root = ItemId::crate_root(CrateId { name });
+        for (id, item) in &crate_.index {
+            let rustdoc_types::ItemEnum::Impl(i) = &item.inner else {
+                continue;
+            };
+            let id = root.crate_sibling(id);
+            if item.crate_id != 0 || !is_trait(&id, i) {
+                continue;
+            }
+            let kind = if is_type(&id, &i.for_) {
+                ImplKind::Direct
+            } else if matches!(i.for_, rustdoc_types::Type::Generic(_)) && !listed_blanket {
+                ImplKind::Blanket
+            } else {
+                continue;
+            };
+            matches.push(ImplMatch {
+                kind,
+                id,
+                item,
+                impl_: i,
+            });
+        }
+    }
+
+    matches.sort_by_key(|m| m.kind);
+    matches
+}
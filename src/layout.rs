@@ -0,0 +1,386 @@
+//! Type sizes and layouts, as reported by the compiler with the nightly-only `-Zprint-type-sizes`
+//! flag.
+//!
+//! rustc only reports the layouts of types it needed to compute while compiling, so types which
+//! are never used in a concrete (non-generic) way in their own crate won't have one.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use anyhow::Context;
+
+use crate::analysis::SUBDIR;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeLayout {
+    pub size: u64,
+    pub align: u64,
+    /// Fields of a struct or union, in the order they're laid out in memory.
+    pub fields: Vec<FieldLayout>,
+    pub end_padding: u64,
+    /// The size of an enum's discriminant, if it's stored separately rather than in a niche of one
+    /// of the variants' fields.
+    pub discriminant: Option<u64>,
+    pub variants: Vec<VariantLayout>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldLayout {
+    pub name: String,
+    pub offset: u64,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariantLayout {
+    pub name: String,
+    pub size: u64,
+}
+
+/// Layouts of types, by crate name and then path within the crate (e.g. `module::Type`).
+#[derive(Debug, Default)]
+pub struct TypeLayouts {
+    crates: HashMap<String, HashMap<String, TypeLayout>>,
+}
+
+impl TypeLayouts {
+    /// Compile the workspace's crates with `-Zprint-type-sizes` and save what it prints.
+    pub fn generate(
+        workspace_path: impl AsRef<Path>,
+        toolchain: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let workspace_path = workspace_path.as_ref();
+        let out_dir = layout_root(workspace_path);
+        fs::create_dir_all(&out_dir).with_context(|| out_dir.display().to_string())?;
+
+        // Build separately from the rustdoc output, so cleaning doesn't remove any of that.
+        let target_dir = Path::new("target").join(SUBDIR).join("layout-build");
+
+        for target in workspace_targets(workspace_path, toolchain)? {
+            // Cargo won't recompile (and so print anything) if the crate is fresh.
+            let status = cargo(toolchain)
+                .arg("clean")
+                .arg("--package")
+                .arg(&target.package)
+                .arg("--target-dir")
+                .arg(&target_dir)
+                .current_dir(workspace_path)
+                .status()
+                .context("failed to run 'cargo clean'")?;
+            if !status.success() {
+                anyhow::bail!("'cargo clean' failed for package {}", target.package);
+            }
+
+            let output = cargo(toolchain)
+                .arg("rustc")
+                .arg("--package")
+                .arg(&target.package)
+                .args(&target.selector)
+                .arg("--target-dir")
+                .arg(&target_dir)
+                .arg("--")
+                .arg("-Zprint-type-sizes")
+                .current_dir(workspace_path)
+                .stderr(Stdio::inherit())
+                .output()
+                .context("failed to run 'cargo rustc'")?;
+            if !output.status.success() {
+                error!(
+                    "'cargo rustc' failed for {}; its type layouts won't be available",
+                    target.crate_name
+                );
+                continue;
+            }
+
+            let path = out_dir.join(format!("{}.txt", target.crate_name));
+            fs::write(&path, output.stdout).with_context(|| path.display().to_string())?;
+        }
+        Ok(())
+    }
+
+    /// Load what a previous call to `generate` saved. Missing data isn't an error; it just means
+    /// there are no layouts.
+    pub fn load(workspace_path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let root = layout_root(workspace_path.as_ref());
+        let mut crates = HashMap::new();
+        let Ok(entries) = fs::read_dir(&root) else {
+            return Ok(Self::default());
+        };
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "txt") {
+                let crate_name = path
+                    .file_stem()
+                    .unwrap()
+                    .to_str()
+                    .ok_or_else(|| anyhow::anyhow!("{path:?} isn't utf-8"))?
+                    .to_owned();
+                let txt = fs::read_to_string(&path).with_context(|| path.display().to_string())?;
+                crates.insert(crate_name, parse(&txt));
+            }
+        }
+        Ok(Self { crates })
+    }
+
+    /// Look up a type by its full path, starting with the crate name.
+    pub fn get(&self, path: &[String]) -> Option<&TypeLayout> {
+        let (crate_name, rest) = path.split_first()?;
+        self.crates.get(crate_name)?.get(&rest.join("::"))
+    }
+}
+
+impl std::fmt::Display for TypeLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "layout: {} bytes, alignment {} bytes",
+            self.size, self.align
+        )?;
+        for field in &self.fields {
+            writeln!(
+                f,
+                "  offset {}: {} ({} bytes)",
+                field.offset, field.name, field.size
+            )?;
+        }
+        if self.end_padding != 0 {
+            writeln!(f, "  end padding: {} bytes", self.end_padding)?;
+        }
+        if !self.variants.is_empty() {
+            match self.discriminant {
+                Some(size) => writeln!(f, "  discriminant: {size} bytes")?,
+                None if self.variants.len() > 1 => writeln!(f, "  discriminant stored in a niche")?,
+                None => (),
+            }
+        }
+        for variant in &self.variants {
+            writeln!(f, "  variant {}: {} bytes", variant.name, variant.size)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse the output of `-Zprint-type-sizes`. Types with generic arguments are skipped.
+pub fn parse(txt: &str) -> HashMap<String, TypeLayout> {
+    let mut layouts = HashMap::new();
+    let mut current: Option<(String, TypeLayout)> = None;
+    let mut offset = 0;
+    for line in txt.lines() {
+        let Some(line) = line.strip_prefix("print-type-size ") else {
+            continue;
+        };
+        let depth = line.len() - line.trim_start().len();
+        let line = line.trim_start();
+
+        if let Some(rest) = line.strip_prefix("type: `") {
+            layouts.extend(current.take());
+            let Some((name, sizes)) = rest.split_once("`: ") else {
+                continue;
+            };
+            if name.contains(['<', '{', '[', '(']) {
+                continue;
+            }
+            let (size, align) = match sizes.split_once(", alignment: ") {
+                Some((size, align)) => (parse_bytes(size), parse_bytes(align)),
+                None => (parse_bytes(sizes), None),
+            };
+            let (Some(size), Some(align)) = (size, align) else {
+                continue;
+            };
+            offset = 0;
+            current = Some((
+                name.to_owned(),
+                TypeLayout {
+                    size,
+                    align,
+                    fields: vec![],
+                    end_padding: 0,
+                    discriminant: None,
+                    variants: vec![],
+                },
+            ));
+            continue;
+        }
+
+        let Some((_, layout)) = &mut current else {
+            continue;
+        };
+        // Fields and padding of enum variants are nested further; only their totals are kept.
+        if depth > 4 {
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix("field `.") {
+            let Some((name, size)) = rest.split_once("`: ") else {
+                continue;
+            };
+            let size = parse_bytes(size).unwrap_or(0);
+            layout.fields.push(FieldLayout {
+                name: name.to_owned(),
+                offset,
+                size,
+            });
+            offset += size;
+        } else if let Some(rest) = line.strip_prefix("padding: ") {
+            offset += parse_bytes(rest).unwrap_or(0);
+        } else if let Some(rest) = line.strip_prefix("end padding: ") {
+            layout.end_padding = parse_bytes(rest).unwrap_or(0);
+        } else if let Some(rest) = line.strip_prefix("discriminant: ") {
+            layout.discriminant = parse_bytes(rest);
+        } else if let Some(rest) = line.strip_prefix("variant `") {
+            if let Some((name, size)) = rest.split_once("`: ") {
+                layout.variants.push(VariantLayout {
+                    name: name.to_owned(),
+                    size: parse_bytes(size).unwrap_or(0),
+                });
+            }
+        }
+    }
+    layouts.extend(current);
+    layouts
+}
+
+/// Parse e.g. "8 bytes" or "8 bytes, alignment: 8 bytes".
+fn parse_bytes(s: &str) -> Option<u64> {
+    s.split(' ').next()?.parse().ok()
+}
+
+fn layout_root(workspace_path: &Path) -> PathBuf {
+    workspace_path.join("target").join(SUBDIR).join("layout")
+}
+
+fn cargo(toolchain: Option<&str>) -> Command {
+    let mut cmd = Command::new("cargo");
+    if let Some(toolchain) = toolchain {
+        cmd.arg(format!("+{toolchain}"));
+    }
+    cmd
+}
+
+/// A crate in the workspace to compile.
+struct Target {
+    package: String,
+    crate_name: String,
+    /// Cargo arguments selecting the target within the package.
+    selector: Vec<String>,
+}
+
+/// Find the library and binary crates of the workspace's packages, like `cargo doc` documents.
+fn workspace_targets(
+    workspace_path: &Path,
+    toolchain: Option<&str>,
+) -> anyhow::Result<Vec<Target>> {
+    let output = cargo(toolchain)
+        .arg("metadata")
+        .arg("--no-deps")
+        .arg("--format-version")
+        .arg("1")
+        .current_dir(workspace_path)
+        .stderr(Stdio::inherit())
+        .output()
+        .context("failed to run 'cargo metadata'")?;
+    if !output.status.success() {
+        anyhow::bail!("'cargo metadata' failed");
+    }
+    let metadata: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .context("failed to parse 'cargo metadata' output")?;
+
+    let mut targets = vec![];
+    for package in metadata["packages"].as_array().into_iter().flatten() {
+        let Some(package_name) = package["name"].as_str() else {
+            continue;
+        };
+        let mut crate_names = vec![];
+        for target in package["targets"].as_array().into_iter().flatten() {
+            let (Some(name), Some(kinds)) = (target["name"].as_str(), target["kind"].as_array())
+            else {
+                continue;
+            };
+            let crate_name = name.replace('-', "_");
+            let selector = if kinds.iter().any(|k| k.as_str().is_some_and(is_lib_kind)) {
+                vec!["--lib".to_owned()]
+            } else if kinds.iter().any(|k| k == "bin") {
+                vec!["--bin".to_owned(), name.to_owned()]
+            } else {
+                continue;
+            };
+            // Like rustdoc, skip binaries with the same name as the library.
+            if crate_names.contains(&crate_name) {
+                continue;
+            }
+            crate_names.push(crate_name.clone());
+            targets.push(Target {
+                package: package_name.to_owned(),
+                crate_name,
+                selector,
+            });
+        }
+    }
+    Ok(targets)
+}
+
+fn is_lib_kind(kind: &str) -> bool {
+    matches!(
+        kind,
+        "lib" | "rlib" | "dylib" | "cdylib" | "staticlib" | "proc-macro"
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        let layouts = parse(
+            "\
+print-type-size type: `inner::Deep`: 16 bytes, alignment: 8 bytes
+print-type-size     field `.b`: 8 bytes
+print-type-size     field `.a`: 1 bytes
+print-type-size     end padding: 7 bytes
+print-type-size type: `std::option::Option<inner::E>`: 8 bytes, alignment: 4 bytes
+print-type-size     variant `Some`: 8 bytes
+print-type-size         field `.0`: 8 bytes
+print-type-size     variant `None`: 0 bytes
+print-type-size type: `inner::E`: 8 bytes, alignment: 4 bytes
+print-type-size     discriminant: 4 bytes
+print-type-size     variant `A`: 4 bytes
+print-type-size         field `.0`: 4 bytes
+print-type-size     variant `B`: 0 bytes
+",
+        );
+        assert_eq!(layouts.len(), 2);
+        assert_eq!(
+            layouts["inner::Deep"],
+            TypeLayout {
+                size: 16,
+                align: 8,
+                fields: vec![
+                    FieldLayout {
+                        name: "b".to_owned(),
+                        offset: 0,
+                        size: 8,
+                    },
+                    FieldLayout {
+                        name: "a".to_owned(),
+                        offset: 8,
+                        size: 1,
+                    },
+                ],
+                end_padding: 7,
+                discriminant: None,
+                variants: vec![],
+            }
+        );
+        assert_eq!(
+            layouts["inner::E"].to_string(),
+            "\
+layout: 8 bytes, alignment 4 bytes
+  discriminant: 4 bytes
+  variant A: 4 bytes
+  variant B: 0 bytes
+"
+        );
+    }
+}
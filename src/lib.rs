@@ -2,7 +2,19 @@
 extern crate log;
 
 pub mod analysis;
+pub mod audit;
+pub mod browser_diff;
 pub mod browser_rustdoc;
 pub mod browser_trait;
+pub mod conversions;
+pub mod coverage;
+pub mod diff;
+pub mod graph;
+pub mod impl_query;
+pub mod layout;
 pub mod scroll_pad;
+pub mod search;
+pub mod semver;
+pub mod substitute;
+pub mod type_search;
 pub mod ui;
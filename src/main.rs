@@ -1,27 +1,121 @@
 use std::io::{self, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 
 use anyhow::Context;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use lazy_static::lazy_static;
 use log::{error, info, Log};
-use rsbrowse::analysis::Analysis;
+use rsbrowse::analysis::{self, Analysis};
+use rsbrowse::browser_diff::DiffBrowser;
 use rsbrowse::browser_rustdoc::RustdocBrowser;
-use rsbrowse::ui;
+use rsbrowse::coverage::{self, Coverage};
+use rsbrowse::layout::TypeLayouts;
+use rsbrowse::{audit, diff, graph, semver, ui};
 use tempfile::NamedTempFile;
 
 #[derive(Debug, Parser)]
-#[command(version)]
+#[command(
+    version,
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
 struct Arguments {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Cargo workspace path
-    #[arg()]
-    workspace_path: PathBuf,
+    #[arg(required = true)]
+    workspace_path: Option<PathBuf>,
 
     /// Select rust toolchain to use.
     /// To disable this flag (i.e. if you don't use rustup), set it to empty string.
-    #[arg(long, default_value = "nightly")]
+    #[arg(long, default_value = "nightly", global = true)]
     toolchain: Option<String>,
+
+    /// Also compile the workspace with `-Zprint-type-sizes` to show the sizes and layouts of its
+    /// types.
+    #[arg(long)]
+    layout: bool,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Compare the public API of two versions of a workspace's crates.
+    Diff(DiffArguments),
+
+    /// Print a graph of the types related to an item, or of how crates depend on each other, as
+    /// Graphviz DOT or Mermaid.
+    Graph(GraphArguments),
+
+    /// List the unsafe functions, traits, impls, mutable statics and extern block items in a
+    /// workspace's crates.
+    Unsafe(UnsafeArguments),
+
+    /// Report how many of the public items in a workspace's crates have doc comments, and list
+    /// the ones that don't.
+    Coverage(CoverageArguments),
+}
+
+#[derive(Debug, clap::Args)]
+struct DiffArguments {
+    /// Old version: path to a Cargo workspace, e.g. a git worktree of an older revision
+    old: PathBuf,
+
+    /// New version: path to a Cargo workspace
+    new: PathBuf,
+
+    /// Treat OLD and NEW as directories of already-generated rustdoc JSON files instead of
+    /// workspaces.
+    #[arg(long)]
+    json_dir: bool,
+
+    /// Print the differences instead of browsing them.
+    #[arg(long)]
+    text: bool,
+
+    /// Classify each difference as a major, minor, or patch change, print a summary, and exit
+    /// with an error if any of them are breaking (major).
+    #[arg(long, conflicts_with = "text")]
+    semver: bool,
+}
+
+#[derive(Debug, clap::Args)]
+struct GraphArguments {
+    /// Cargo workspace path
+    workspace_path: PathBuf,
+
+    /// Full path of the type or trait to start from, e.g. `mycrate::module::Type`. If not given,
+    /// graph the dependencies between all the crates instead.
+    item: Option<String>,
+
+    /// How many steps away from the item to follow relationships.
+    #[arg(long, default_value_t = 2)]
+    depth: usize,
+
+    /// Print Mermaid instead of DOT.
+    #[arg(long)]
+    mermaid: bool,
+}
+
+#[derive(Debug, clap::Args)]
+struct UnsafeArguments {
+    /// Cargo workspace path
+    workspace_path: PathBuf,
+}
+
+#[derive(Debug, clap::Args)]
+struct CoverageArguments {
+    /// Cargo workspace path
+    workspace_path: PathBuf,
+
+    /// Print the report as JSON.
+    #[arg(long)]
+    json: bool,
+
+    /// Exit with an error if less than this percent of the public items are documented.
+    #[arg(long, value_name = "PERCENT")]
+    min: Option<f64>,
 }
 
 fn main() -> anyhow::Result<()> {
@@ -34,19 +128,172 @@ fn main() -> anyhow::Result<()> {
     log::set_max_level(log::LevelFilter::max());
     let _ = log::set_logger(&*LOGGER);
 
+    match &args.command {
+        Some(Command::Diff(diff_args)) => return run_diff(diff_args, &args),
+        Some(Command::Graph(graph_args)) => return run_graph(graph_args, &args),
+        Some(Command::Unsafe(unsafe_args)) => return run_unsafe(unsafe_args, &args),
+        Some(Command::Coverage(coverage_args)) => return run_coverage(coverage_args, &args),
+        None => (),
+    }
+
+    let workspace_path = args
+        .workspace_path
+        .as_ref()
+        .expect("workspace path is required");
+
     eprintln!("Running Cargo to generate analysis data...");
-    Analysis::generate(&args.workspace_path, args.toolchain.as_deref())?;
+    Analysis::generate(workspace_path, args.toolchain.as_deref())?;
 
     eprintln!("Reading analysis data...");
-    let analysis = Analysis::load(&args.workspace_path)?;
+    let analysis = Analysis::load(workspace_path)?;
 
-    std::env::set_current_dir(&args.workspace_path)?;
+    let mut layouts = TypeLayouts::default();
+    if args.layout {
+        eprintln!("Running Cargo to get type layouts...");
+        match TypeLayouts::generate(workspace_path, args.toolchain.as_deref()) {
+            Ok(()) => layouts = TypeLayouts::load(workspace_path)?,
+            Err(e) => {
+                error!("Error getting type layouts: {e:#}");
+                error!("Type layouts will not be shown.");
+            }
+        }
+    }
+
+    let output_dir = analysis::output_dir(&std::path::absolute(workspace_path)?);
+    std::env::set_current_dir(workspace_path)?;
 
-    let browser = RustdocBrowser::new(analysis);
+    let browser = RustdocBrowser::new(analysis)
+        .with_layouts(layouts)
+        .with_output_dir(output_dir);
 
     // Mega-hax, but doesn't matter because we're not returning from run() anyway.
     let browser: &'static RustdocBrowser = Box::leak(Box::new(browser));
 
+    start_logging(&args);
+
+    ui::run(browser);
+    Ok(())
+}
+
+fn run_diff(diff_args: &DiffArguments, args: &Arguments) -> anyhow::Result<()> {
+    let load = |path: &Path| -> anyhow::Result<Analysis> {
+        if diff_args.json_dir {
+            eprintln!("Reading analysis data from {path:?}...");
+            return Analysis::load_json_dir(path);
+        }
+        eprintln!("Running Cargo to generate analysis data for {path:?}...");
+        Analysis::generate(path, args.toolchain.as_deref())?;
+        eprintln!("Reading analysis data for {path:?}...");
+        Analysis::load(path)
+    };
+
+    // More mega-hax: the changes borrow from these for as long as the program runs.
+    let old: &'static Analysis = Box::leak(Box::new(load(&diff_args.old)?));
+    let new: &'static Analysis = Box::leak(Box::new(load(&diff_args.new)?));
+
+    let changes = diff::diff(old, new);
+
+    if diff_args.text {
+        diff::write_text(&changes, &mut io::stdout().lock())?;
+        return Ok(());
+    }
+
+    if diff_args.semver {
+        let severity = semver::write_summary(&changes, new, &mut io::stdout().lock())?;
+        if severity == semver::Severity::Major {
+            anyhow::bail!("found breaking changes");
+        }
+        return Ok(());
+    }
+
+    if changes.is_empty() {
+        eprintln!("No public API changes.");
+        return Ok(());
+    }
+
+    // Source paths in JSON from elsewhere are relative to wherever it was generated; the best
+    // guess is the current directory.
+    let (old_root, new_root) = if diff_args.json_dir {
+        (PathBuf::new(), PathBuf::new())
+    } else {
+        (diff_args.old.clone(), diff_args.new.clone())
+    };
+    let browser = DiffBrowser::new(changes, old_root, new_root);
+    let browser: &'static DiffBrowser = Box::leak(Box::new(browser));
+
+    start_logging(args);
+
+    ui::run(browser);
+    Ok(())
+}
+
+fn run_graph(graph_args: &GraphArguments, args: &Arguments) -> anyhow::Result<()> {
+    let workspace_path = &graph_args.workspace_path;
+    eprintln!("Running Cargo to generate analysis data...");
+    Analysis::generate(workspace_path, args.toolchain.as_deref())?;
+    eprintln!("Reading analysis data...");
+    let analysis = Analysis::load(workspace_path)?;
+
+    let graph = if let Some(item) = &graph_args.item {
+        let path = item.split("::").collect::<Vec<_>>();
+        let id = analysis
+            .find_path(&path)
+            .with_context(|| format!("no item found at {item}"))?;
+        let graph = graph::type_graph(&analysis, id, graph_args.depth);
+        if graph.nodes.is_empty() {
+            anyhow::bail!("{item} is not a type or trait");
+        }
+        graph
+    } else {
+        graph::crate_graph(&analysis)
+    };
+
+    let format = if graph_args.mermaid {
+        graph::Format::Mermaid
+    } else {
+        graph::Format::Dot
+    };
+    graph.write(format, &mut io::stdout().lock())?;
+    Ok(())
+}
+
+fn run_unsafe(unsafe_args: &UnsafeArguments, args: &Arguments) -> anyhow::Result<()> {
+    let workspace_path = &unsafe_args.workspace_path;
+    eprintln!("Running Cargo to generate analysis data...");
+    Analysis::generate(workspace_path, args.toolchain.as_deref())?;
+    eprintln!("Reading analysis data...");
+    let analysis = Analysis::load(workspace_path)?;
+    audit::write_report(&analysis, &mut io::stdout().lock())?;
+    Ok(())
+}
+
+fn run_coverage(coverage_args: &CoverageArguments, args: &Arguments) -> anyhow::Result<()> {
+    let workspace_path = &coverage_args.workspace_path;
+    eprintln!("Running Cargo to generate analysis data...");
+    Analysis::generate(workspace_path, args.toolchain.as_deref())?;
+    eprintln!("Reading analysis data...");
+    let analysis = Analysis::load(workspace_path)?;
+
+    let crates = coverage::workspace_coverage(&analysis);
+    if coverage_args.json {
+        let mut out = io::stdout().lock();
+        serde_json::to_writer_pretty(&mut out, &coverage::to_json(&crates))?;
+        writeln!(out)?;
+    } else {
+        coverage::write_text(&crates, &mut io::stdout().lock())?;
+    }
+
+    if let Some(min) = coverage_args.min {
+        let percent = crates.percent();
+        if percent < min {
+            anyhow::bail!("documentation coverage {percent:.1}% is below the minimum of {min}%");
+        }
+    }
+    Ok(())
+}
+
+/// Redirect logging to a file, because stderr isn't visible while the UI is running.
+fn start_logging(args: &Arguments) {
     if let Err(e) = log_to_file() {
         eprintln!("failed to set up logging to file: {e}");
         eprintln!("disabling logs");
@@ -60,9 +307,6 @@ fn main() -> anyhow::Result<()> {
         info!("{args:#?}");
         info!("workspace path: {:?}", std::env::current_dir());
     }
-
-    ui::run(browser);
-    Ok(())
 }
 
 fn log_to_file() -> anyhow::Result<()> {
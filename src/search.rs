@@ -0,0 +1,218 @@
+//! Full-text search of the docs of all the loaded crates.
+
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+use crate::analysis::{self, Analysis, CrateId, ItemId};
+
+/// How much of the docs to show before and after a match.
+const SNIPPET_BEFORE: usize = 30;
+const SNIPPET_AFTER: usize = 50;
+
+/// Which items' docs contain each word.
+#[derive(Debug, Default)]
+pub struct DocIndex {
+    crate_names: Vec<String>,
+    /// Lowercased words, and the items with them, by index into `crate_names` and ID.
+    words: HashMap<String, Vec<(usize, rustdoc_types::Id)>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DocMatch<'a> {
+    pub id: ItemId<'a>,
+    pub item: &'a rustdoc_types::Item,
+    pub kind: &'static str,
+    /// Full path of the item.
+    pub path: String,
+    /// Part of the docs around the match, with whitespace collapsed.
+    pub snippet: String,
+    /// Where the match is in the snippet: either the whole query, or the first of its words.
+    pub highlight: Range<usize>,
+    /// Whether the docs contain the query as a phrase, rather than just all of its words.
+    pub phrase: bool,
+}
+
+impl DocIndex {
+    pub fn build(analysis: &Analysis) -> Self {
+        let mut crate_names = analysis.crates.keys().cloned().collect::<Vec<_>>();
+        crate_names.sort();
+        let mut words: HashMap<String, Vec<(usize, rustdoc_types::Id)>> = HashMap::new();
+        for (crate_idx, name) in crate_names.iter().enumerate() {
+            for (id, item) in &analysis.crates[name].index {
+                // Items copied from other crates are indexed with those crates.
+                if item.crate_id != 0 {
+                    continue;
+                }
+                let Some(docs) = &item.docs else {
+                    continue;
+                };
+                for word in tokenize(docs).collect::<HashSet<_>>() {
+                    words.entry(word).or_default().push((crate_idx, *id));
+                }
+            }
+        }
+        Self { crate_names, words }
+    }
+
+    /// Find the items whose docs contain all the words of the query, with the ones containing it
+    /// as a phrase first.
+    pub fn search<'a>(&self, analysis: &'a Analysis, query: &str) -> Vec<DocMatch<'a>> {
+        let query_words = tokenize(query).collect::<HashSet<_>>();
+        let mut postings = query_words
+            .iter()
+            .map(|word| self.words.get(word).map(Vec::as_slice).unwrap_or_default())
+            .collect::<Vec<_>>();
+        postings.sort_by_key(|p| p.len());
+        let Some((rarest, rest)) = postings.split_first() else {
+            return vec![];
+        };
+        let rest = rest
+            .iter()
+            .map(|p| p.iter().collect::<HashSet<_>>())
+            .collect::<Vec<_>>();
+
+        let query_flat = flatten(query).to_lowercase();
+        let first_word = tokenize(query).next().unwrap_or_default();
+        let mut matches = rarest
+            .iter()
+            .filter(|posting| rest.iter().all(|p| p.contains(posting)))
+            .filter_map(|(crate_idx, id)| {
+                let (name, crate_) = analysis
+                    .crates
+                    .get_key_value(&self.crate_names[*crate_idx])?;
+                let (id, item) = crate_.index.get_key_value(id)?;
+                let id = ItemId::crate_root(CrateId { name }).crate_sibling(id);
+                let flat = flatten(item.docs.as_deref()?);
+                let (lower, offsets) = lowercase(&flat);
+                let (range, phrase) = match lower.find(&query_flat) {
+                    Some(start) => (start..start + query_flat.len(), true),
+                    None => (find_word(&lower, &first_word)?, false),
+                };
+                let (snippet, highlight) = snippet(&flat, offsets[range.start]..offsets[range.end]);
+                Some(DocMatch {
+                    kind: analysis::kind_name(item),
+                    path: analysis.full_name(&id, item),
+                    id,
+                    item,
+                    snippet,
+                    highlight,
+                    phrase,
+                })
+            })
+            .collect::<Vec<_>>();
+        matches.sort_by(|a, b| b.phrase.cmp(&a.phrase).then_with(|| a.path.cmp(&b.path)));
+        matches
+    }
+}
+
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+}
+
+/// Remove Markdown code, emphasis, and link marks and collapse whitespace, so phrases match across
+/// them.
+fn flatten(text: &str) -> String {
+    text.replace(['`', '*', '[', ']'], "")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Lowercase text the same way as the words in the index, along with where each byte of the result
+/// (and its end) came from in the original, as lowercasing can change the length of characters.
+fn lowercase(text: &str) -> (String, Vec<usize>) {
+    let mut lower = String::with_capacity(text.len());
+    let mut offsets = Vec::with_capacity(text.len() + 1);
+    for (offset, c) in text.char_indices() {
+        lower.extend(c.to_lowercase());
+        offsets.resize(lower.len(), offset);
+    }
+    offsets.push(text.len());
+    (lower, offsets)
+}
+
+/// Find a whole word (not just part of one) in lowercased text.
+fn find_word(text: &str, word: &str) -> Option<Range<usize>> {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+    text.match_indices(word)
+        .map(|(start, _)| start..start + word.len())
+        .find(|range| {
+            !text[..range.start]
+                .chars()
+                .next_back()
+                .is_some_and(is_word_char)
+                && !text[range.end..].chars().next().is_some_and(is_word_char)
+        })
+}
+
+/// Cut out the part of the text around a match, and return it with where the match is in it.
+fn snippet(text: &str, range: Range<usize>) -> (String, Range<usize>) {
+    let mut start = range.start.saturating_sub(SNIPPET_BEFORE);
+    while !text.is_char_boundary(start) {
+        start -= 1;
+    }
+    let mut end = (range.end + SNIPPET_AFTER).min(text.len());
+    while !text.is_char_boundary(end) {
+        end += 1;
+    }
+    // Don't cut words in half.
+    if start != 0 {
+        start = text[start..range.start]
+            .find(' ')
+            .map_or(range.start, |space| start + space + 1);
+    }
+    if end != text.len() {
+        end = text[range.end..end]
+            .rfind(' ')
+            .map_or(range.end, |space| range.end + space);
+    }
+    let mut snippet = String::new();
+    if start != 0 {
+        snippet.push('…');
+    }
+    let offset = snippet.len();
+    snippet += &text[start..end];
+    if end != text.len() {
+        snippet.push('…');
+    }
+    let highlight = range.start - start + offset..range.end - start + offset;
+    (snippet, highlight)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn snippet_test() {
+        let text = flatten(
+            "Pushes a byte.\n\nReturns `None` if the buffer is full, and otherwise returns the \
+            number of bytes left, which may be zero.",
+        );
+        let (lower, _) = lowercase(&text);
+        let range = lower.find("returns none if").unwrap();
+        let (snippet, highlight) = snippet(&text, range..range + 15);
+        assert_eq!(
+            snippet,
+            "Pushes a byte. Returns None if the buffer is full, and otherwise returns the…"
+        );
+        assert_eq!(&snippet[highlight], "Returns None if");
+
+        assert_eq!(find_word(&lower, "byte"), Some(9..13));
+        assert_eq!(find_word(&lower, "by"), None);
+    }
+
+    #[test]
+    fn lowercase_test() {
+        // 'Ü' is two bytes either way, but 'İ' lowercases to two characters, three bytes in all.
+        let text = "Über İstanbul";
+        let (lower, offsets) = lowercase(text);
+        assert_eq!(lower, "über i\u{307}stanbul");
+        let range = find_word(&lower, "über").unwrap();
+        assert_eq!(&text[offsets[range.start]..offsets[range.end]], "Über");
+        let start = lower.find("stanbul").unwrap();
+        assert_eq!(&text[offsets[start]..offsets[lower.len()]], "stanbul");
+    }
+}
@@ -0,0 +1,275 @@
+//! Classifying API changes by which part of a semver version they require bumping, following
+//! the rules in the Cargo book's "SemVer Compatibility" chapter.
+
+use crate::analysis::{Analysis, Item};
+use crate::browser_rustdoc::{generics_label, where_predicate_label};
+use crate::diff::{fn_signature, ApiItem, Change, ChangeKind};
+use std::collections::HashSet;
+use std::io::{self, Write};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Patch,
+    Minor,
+    Major,
+}
+
+impl Severity {
+    pub fn name(self) -> &'static str {
+        match self {
+            Severity::Patch => "patch",
+            Severity::Minor => "minor",
+            Severity::Major => "major",
+        }
+    }
+}
+
+/// Determine how severe a change is, and why. `new` is the analysis of the new version, which is
+/// used to look up the parents of added items.
+pub fn classify(change: &Change, new: &Analysis) -> (Severity, &'static str) {
+    match (change.kind, &change.old, &change.new) {
+        (ChangeKind::Removed, Some(old), _) => classify_removed(old),
+        (ChangeKind::Added, _, Some(new_item)) => classify_added(new_item, new),
+        (ChangeKind::Changed, Some(old), Some(new_item)) => classify_changed(old, new_item),
+        _ => (Severity::Major, "unknown change"),
+    }
+}
+
+fn classify_removed(old: &ApiItem) -> (Severity, &'static str) {
+    match &old.item.inner {
+        rustdoc_types::ItemEnum::Impl(_) => (Severity::Major, "trait impl removed"),
+        rustdoc_types::ItemEnum::Variant(_) => (Severity::Major, "enum variant removed"),
+        rustdoc_types::ItemEnum::StructField(_) => (Severity::Major, "public field removed"),
+        _ => (Severity::Major, "public item removed"),
+    }
+}
+
+fn classify_added(item: &ApiItem, new: &Analysis) -> (Severity, &'static str) {
+    use rustdoc_types::ItemEnum::*;
+    let parent = match new.parent(&item.id).and_then(|id| new.get_item(id)) {
+        Some((_, Item::Item(parent))) => Some(parent),
+        _ => None,
+    };
+    match (&item.item.inner, parent.map(|p| &p.inner)) {
+        (Variant(_), Some(Enum(_))) => {
+            if is_non_exhaustive(parent.unwrap()) {
+                (Severity::Minor, "variant added to #[non_exhaustive] enum")
+            } else {
+                (Severity::Major, "variant added to exhaustive enum")
+            }
+        }
+        (StructField(_), Some(Struct(s))) => {
+            let parent = parent.unwrap();
+            let has_private_fields = match &s.kind {
+                rustdoc_types::StructKind::Unit => false,
+                rustdoc_types::StructKind::Tuple(fields) => fields.iter().any(|f| {
+                    f.as_ref()
+                        .is_none_or(|f| !is_field_public(new, &item.id, f))
+                }),
+                rustdoc_types::StructKind::Plain {
+                    fields,
+                    has_stripped_fields,
+                } => {
+                    *has_stripped_fields
+                        || fields.iter().any(|f| !is_field_public(new, &item.id, f))
+                }
+            };
+            if has_private_fields || is_non_exhaustive(parent) {
+                (
+                    Severity::Minor,
+                    "public field added to struct with private fields",
+                )
+            } else {
+                (
+                    Severity::Major,
+                    "public field added to struct with all public fields",
+                )
+            }
+        }
+        (Function(f), Some(Trait(_))) if !f.has_body => {
+            (Severity::Major, "required trait method added")
+        }
+        (AssocType { type_: None, .. }, Some(Trait(_))) => (
+            Severity::Major,
+            "associated type without default added to trait",
+        ),
+        (AssocConst { value: None, .. }, Some(Trait(_))) => (
+            Severity::Major,
+            "associated const without default added to trait",
+        ),
+        (Function(_), Some(Trait(_))) => (Severity::Minor, "provided trait method added"),
+        (Impl(_), _) => (Severity::Minor, "trait impl added"),
+        _ => (Severity::Minor, "public item added"),
+    }
+}
+
+fn classify_changed(old: &ApiItem, new: &ApiItem) -> (Severity, &'static str) {
+    use rustdoc_types::ItemEnum::*;
+    match (&old.item.inner, &new.item.inner) {
+        (Function(o), Function(n)) => {
+            let name = new.item.name.as_deref().unwrap_or("_");
+            if fn_signature(name, o) == fn_signature(name, n) {
+                if o.has_body {
+                    (
+                        Severity::Major,
+                        "trait method's default implementation removed",
+                    )
+                } else {
+                    (
+                        Severity::Minor,
+                        "trait method given a default implementation",
+                    )
+                }
+            } else {
+                (Severity::Major, "function signature changed")
+            }
+        }
+        (Impl(o), Impl(n)) => classify_impl_change(o, n),
+        (o, n) if std::mem::discriminant(o) != std::mem::discriminant(n) => {
+            (Severity::Major, "kind of item changed")
+        }
+        _ => {
+            if is_non_exhaustive(new.item) && !is_non_exhaustive(old.item) {
+                return (Severity::Major, "#[non_exhaustive] added");
+            }
+            let private_fields = "/* private fields */";
+            if new.signature.contains(private_fields) && !old.signature.contains(private_fields) {
+                return (Severity::Major, "private fields added to struct");
+            }
+            if let Some(result) = classify_generics_change(old, new) {
+                return result;
+            }
+            if !is_non_exhaustive(new.item) && is_non_exhaustive(old.item) {
+                return (Severity::Minor, "#[non_exhaustive] removed");
+            }
+            if old.signature.contains(private_fields) && !new.signature.contains(private_fields) {
+                return (Severity::Minor, "struct fields all made public");
+            }
+            (Severity::Major, "declaration changed")
+        }
+    }
+}
+
+/// Impls' signatures change whenever the implementing type's generics do, so only look at their
+/// where-clauses, ignoring bounds on parameters the old impl didn't have.
+fn classify_impl_change(
+    old: &rustdoc_types::Impl,
+    new: &rustdoc_types::Impl,
+) -> (Severity, &'static str) {
+    let old_params = old
+        .generics
+        .params
+        .iter()
+        .map(|p| p.name.as_str())
+        .collect::<HashSet<_>>();
+    let old_preds = old
+        .generics
+        .where_predicates
+        .iter()
+        .map(where_predicate_label)
+        .collect::<HashSet<_>>();
+    let new_preds = new
+        .generics
+        .where_predicates
+        .iter()
+        .filter(|pred| match pred {
+            rustdoc_types::WherePredicate::BoundPredicate {
+                type_: rustdoc_types::Type::Generic(name),
+                ..
+            } => old_params.contains(name.as_str()),
+            _ => true,
+        })
+        .map(where_predicate_label)
+        .collect::<HashSet<_>>();
+    if new_preds == old_preds {
+        (Severity::Patch, "impl changed only to match its type")
+    } else if new_preds.is_subset(&old_preds) {
+        (Severity::Minor, "impl bounds loosened")
+    } else {
+        (Severity::Major, "impl bounds tightened")
+    }
+}
+
+/// If the only difference between the items is that generic parameters were added to the end of
+/// their list, classify that.
+fn classify_generics_change(old: &ApiItem, new: &ApiItem) -> Option<(Severity, &'static str)> {
+    let (old_generics, new_generics) = (generics(old.item)?, generics(new.item)?);
+    let added = new_generics.params.strip_prefix(&old_generics.params[..])?;
+    if added.is_empty()
+        || old_generics.where_predicates != new_generics.where_predicates
+        || old.signature.replacen(&generics_label(old_generics), "", 1)
+            != new.signature.replacen(&generics_label(new_generics), "", 1)
+    {
+        return None;
+    }
+    let all_defaulted = added.iter().all(|p| match &p.kind {
+        rustdoc_types::GenericParamDefKind::Lifetime { .. } => false,
+        rustdoc_types::GenericParamDefKind::Type { default, .. } => default.is_some(),
+        rustdoc_types::GenericParamDefKind::Const { default, .. } => default.is_some(),
+    });
+    Some(if all_defaulted {
+        (Severity::Minor, "generic parameter with a default added")
+    } else {
+        (Severity::Major, "generic parameter without a default added")
+    })
+}
+
+fn generics(item: &rustdoc_types::Item) -> Option<&rustdoc_types::Generics> {
+    use rustdoc_types::ItemEnum::*;
+    match &item.inner {
+        Struct(s) => Some(&s.generics),
+        Enum(e) => Some(&e.generics),
+        Union(u) => Some(&u.generics),
+        Trait(t) => Some(&t.generics),
+        TypeAlias(t) => Some(&t.generics),
+        Function(f) => Some(&f.generics),
+        _ => None,
+    }
+}
+
+fn is_non_exhaustive(item: &rustdoc_types::Item) -> bool {
+    item.attrs.iter().any(|attr| attr == "#[non_exhaustive]")
+}
+
+fn is_field_public(
+    analysis: &Analysis,
+    sibling: &crate::analysis::ItemId,
+    field: &rustdoc_types::Id,
+) -> bool {
+    matches!(
+        analysis.get_item(sibling.crate_sibling(field)),
+        Some((_, Item::Item(f))) if f.visibility == rustdoc_types::Visibility::Public
+    )
+}
+
+/// Print each change with its classification, followed by the overall version bump needed.
+/// Returns that overall severity.
+pub fn write_summary(
+    changes: &[Change],
+    new: &Analysis,
+    out: &mut impl Write,
+) -> io::Result<Severity> {
+    let mut overall = Severity::Patch;
+    let mut counts = [0; 3];
+    for change in changes {
+        let (severity, reason) = classify(change, new);
+        overall = overall.max(severity);
+        counts[severity as usize] += 1;
+        writeln!(
+            out,
+            "{} {} {}: {reason}",
+            severity.name(),
+            change.kind.marker(),
+            change.path.join("::")
+        )?;
+    }
+    writeln!(
+        out,
+        "required version bump: {} ({} major, {} minor, {} patch changes)",
+        overall.name(),
+        counts[Severity::Major as usize],
+        counts[Severity::Minor as usize],
+        counts[Severity::Patch as usize],
+    )?;
+    Ok(overall)
+}
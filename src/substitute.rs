@@ -0,0 +1,243 @@
+//! Substituting the concrete generic arguments a type was reached through, like the `Foo` in a
+//! field of type `Vec<Foo>`, for its generic parameters.
+
+use std::sync::Arc;
+
+use rustdoc_types::{GenericArg, GenericArgs, GenericParamDef, GenericParamDefKind, Type};
+
+use crate::analysis::{Analysis, CrateId, Item, ItemId};
+
+/// Concrete types for generic parameters of a type, or of an impl of it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Substitution<'a> {
+    bindings: Vec<Binding<'a>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Binding<'a> {
+    param: &'a str,
+    ty: &'a Type,
+    /// The crate the type was written in, which its IDs belong to.
+    crate_id: CrateId<'a>,
+    /// What the type's own generic parameters stand for, if it was written in a generic item.
+    outer: Option<Arc<Substitution<'a>>>,
+}
+
+impl<'a> Substitution<'a> {
+    fn get(&self, param: &str) -> Option<&Binding<'a>> {
+        self.bindings.iter().find(|b| b.param == param)
+    }
+
+    /// Match a type's generic parameters with the arguments a path to it gave them, in the crate
+    /// of `from`, where the arguments may use the generic parameters of `outer`.
+    fn new(
+        params: &'a [GenericParamDef],
+        args: Option<&'a GenericArgs>,
+        from: &ItemId<'a>,
+        outer: Option<&Arc<Substitution<'a>>>,
+    ) -> Option<Arc<Self>> {
+        let Some(GenericArgs::AngleBracketed { args, .. }) = args else {
+            return None;
+        };
+        let args = args.iter().filter_map(|arg| match arg {
+            GenericArg::Type(ty) => Some(ty),
+            _ => None,
+        });
+        let bindings = type_params(params)
+            .zip(args)
+            .filter_map(|(param, ty)| match ty {
+                Type::Generic(name) => {
+                    // Pass on what the outer parameter stands for, if anything.
+                    let binding = outer?.get(name)?;
+                    Some(Binding {
+                        param,
+                        ..binding.clone()
+                    })
+                }
+                _ => Some(Binding {
+                    param,
+                    ty,
+                    crate_id: from.crate_id(),
+                    outer: outer.cloned(),
+                }),
+            })
+            .collect::<Vec<_>>();
+        (!bindings.is_empty()).then(|| Arc::new(Self { bindings }))
+    }
+
+    /// Carry a type's substitution over to an impl of it, by matching the type's parameters with
+    /// the impl's in the type the impl is for, e.g. `T` in `impl<T> Vec<T>`.
+    pub fn for_impl(
+        &self,
+        params: &'a [GenericParamDef],
+        impl_: &'a rustdoc_types::Impl,
+    ) -> Option<Arc<Self>> {
+        let Type::ResolvedPath(for_) = &impl_.for_ else {
+            return None;
+        };
+        let Some(GenericArgs::AngleBracketed { args, .. }) = for_.args.as_deref() else {
+            return None;
+        };
+        let args = args.iter().filter_map(|arg| match arg {
+            GenericArg::Type(ty) => Some(ty),
+            _ => None,
+        });
+        let bindings = type_params(params)
+            .zip(args)
+            .filter_map(|(param, arg)| match arg {
+                Type::Generic(name) => Some(Binding {
+                    param: name.as_str(),
+                    ..self.get(param)?.clone()
+                }),
+                // Impls for particular arguments, like `impl Vec<u8>`, have no parameter to bind.
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        (!bindings.is_empty()).then(|| Arc::new(Self { bindings }))
+    }
+
+    /// A copy of the type, with the generic parameters replaced.
+    pub fn apply(&self, ty: &Type) -> Type {
+        let mut ty = ty.clone();
+        self.substitute(&mut ty);
+        ty
+    }
+
+    /// A copy of the item, with the generic parameters in its type, signature, or trait (and an
+    /// impl's where clause) replaced.
+    pub fn apply_item(&self, item: &rustdoc_types::Item) -> rustdoc_types::Item {
+        use rustdoc_types::ItemEnum::*;
+        let mut item = item.clone();
+        match &mut item.inner {
+            StructField(ty) => self.substitute(ty),
+            Function(f) => {
+                for (_, ty) in &mut f.sig.inputs {
+                    self.substitute(ty);
+                }
+                if let Some(ty) = &mut f.sig.output {
+                    self.substitute(ty);
+                }
+            }
+            AssocConst { type_, .. } => self.substitute(type_),
+            AssocType {
+                type_: Some(type_), ..
+            } => self.substitute(type_),
+            Impl(i) => {
+                if let Some(args) = i.trait_.as_mut().and_then(|t| t.args.as_deref_mut()) {
+                    self.substitute_args(args);
+                }
+                self.substitute(&mut i.for_);
+                for pred in &mut i.generics.where_predicates {
+                    if let rustdoc_types::WherePredicate::BoundPredicate { type_, .. } = pred {
+                        self.substitute(type_);
+                    }
+                }
+            }
+            _ => (),
+        }
+        item
+    }
+
+    fn substitute(&self, ty: &mut Type) {
+        match ty {
+            Type::Generic(name) => {
+                if let Some(binding) = self.get(name) {
+                    *ty = match &binding.outer {
+                        Some(outer) => outer.apply(binding.ty),
+                        None => binding.ty.clone(),
+                    };
+                }
+            }
+            Type::ResolvedPath(p) => {
+                if let Some(args) = p.args.as_deref_mut() {
+                    self.substitute_args(args);
+                }
+            }
+            Type::FunctionPointer(f) => {
+                for (_, ty) in &mut f.sig.inputs {
+                    self.substitute(ty);
+                }
+                if let Some(ty) = &mut f.sig.output {
+                    self.substitute(ty);
+                }
+            }
+            Type::Tuple(types) => types.iter_mut().for_each(|ty| self.substitute(ty)),
+            Type::Slice(type_)
+            | Type::Array { type_, .. }
+            | Type::Pat { type_, .. }
+            | Type::RawPointer { type_, .. }
+            | Type::BorrowedRef { type_, .. } => self.substitute(type_),
+            Type::QualifiedPath { self_type, .. } => self.substitute(self_type),
+            Type::DynTrait(_) | Type::Primitive(_) | Type::ImplTrait(_) | Type::Infer => (),
+        }
+    }
+
+    fn substitute_args(&self, args: &mut GenericArgs) {
+        match args {
+            GenericArgs::AngleBracketed { args, .. } => {
+                for arg in args {
+                    if let GenericArg::Type(ty) = arg {
+                        self.substitute(ty);
+                    }
+                }
+            }
+            GenericArgs::Parenthesized { inputs, output } => {
+                inputs.iter_mut().for_each(|ty| self.substitute(ty));
+                if let Some(ty) = output {
+                    self.substitute(ty);
+                }
+            }
+            GenericArgs::ReturnTypeNotation => (),
+        }
+    }
+}
+
+/// The names of the type parameters, leaving out lifetimes and consts.
+fn type_params(params: &[GenericParamDef]) -> impl Iterator<Item = &str> {
+    params
+        .iter()
+        .filter(|p| matches!(p.kind, GenericParamDefKind::Type { .. }))
+        .map(|p| p.name.as_str())
+}
+
+/// The generic parameters of a struct, enum, or union.
+pub fn type_generics(item: &rustdoc_types::Item) -> Option<&rustdoc_types::Generics> {
+    use rustdoc_types::ItemEnum::*;
+    match &item.inner {
+        Struct(s) => Some(&s.generics),
+        Enum(e) => Some(&e.generics),
+        Union(u) => Some(&u.generics),
+        _ => None,
+    }
+}
+
+/// The type a type written in the crate of `from` refers to, looking through references,
+/// pointers, slices, and arrays, along with what its generic parameters stand for, if the type
+/// gave them arguments.
+pub fn target<'a>(
+    analysis: &'a Analysis,
+    from: &ItemId<'a>,
+    ty: &'a Type,
+    subst: Option<&Arc<Substitution<'a>>>,
+) -> Option<(ItemId<'a>, Option<Arc<Substitution<'a>>>)> {
+    match ty {
+        Type::Slice(type_)
+        | Type::Array { type_, .. }
+        | Type::RawPointer { type_, .. }
+        | Type::BorrowedRef { type_, .. } => target(analysis, from, type_, subst),
+        Type::Generic(name) => {
+            let binding = subst?.get(name)?;
+            let from = ItemId::crate_root(binding.crate_id.clone());
+            target(analysis, &from, binding.ty, binding.outer.as_ref())
+        }
+        Type::ResolvedPath(p) => {
+            let (id, Item::Item(item)) = analysis.get_item(from.crate_sibling(&p.id))? else {
+                return None;
+            };
+            let subst = type_generics(item)
+                .and_then(|g| Substitution::new(&g.params, p.args.as_deref(), from, subst));
+            Some((id, subst))
+        }
+        _ => None,
+    }
+}
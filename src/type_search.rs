@@ -0,0 +1,557 @@
+//! Searching for functions by the types they take and return, like `&str -> Result<_, Error>`.
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, bail};
+
+use crate::analysis::{Analysis, CrateId, Item, ItemId};
+use crate::diff::fn_signature;
+
+/// How far from an exact match a function can be in various ways. Results are ranked by the sum.
+const VAR_COST: u32 = 1;
+const REORDER_COST: u32 = 1;
+const DYN_COST: u32 = 1;
+const GENERIC_COST: u32 = 2;
+/// A generic type argument, like the `T` in `Vec<T>`, is more specific than a generic type.
+const GENERIC_ARG_COST: u32 = 1;
+const REF_COST: u32 = 2;
+/// An array matched by a slice pattern, like `[u8]` for `[u8; 4]`.
+const ARRAY_COST: u32 = 1;
+const WRAPPED_COST: u32 = 3;
+
+/// Don't try reordering the arguments of functions with more than this many.
+const MAX_REORDERED_ARGS: usize = 6;
+
+/// A type to look for in function signatures.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypePattern {
+    /// `_`, matching any type.
+    Any,
+    /// A single capital letter, like `T`, standing for any type, but the same one everywhere it
+    /// appears.
+    Var(String),
+    /// A type or primitive, matched by the last part of its path, and optionally its generic
+    /// arguments. Arguments left off match anything.
+    Named {
+        name: String,
+        args: Vec<TypePattern>,
+    },
+    Ref {
+        mutable: bool,
+        inner: Box<TypePattern>,
+    },
+    /// Slices, and arrays with a cost.
+    Slice(Box<TypePattern>),
+    Tuple(Vec<TypePattern>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignatureQuery {
+    pub inputs: Vec<TypePattern>,
+    /// What the function returns. If `None`, it can return anything.
+    pub output: Option<TypePattern>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SignatureMatch<'a> {
+    pub id: ItemId<'a>,
+    pub item: &'a rustdoc_types::Item,
+    /// Full path of the function.
+    pub path: String,
+    pub signature: String,
+    /// How far it is from an exact match; 0 is exact.
+    pub cost: u32,
+}
+
+impl std::str::FromStr for SignatureQuery {
+    type Err = anyhow::Error;
+
+    /// Parse e.g. `&str, usize -> Option<String>`. Without an arrow, it's just the inputs.
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let tokens = lex(s)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let mut inputs = vec![];
+        while !matches!(parser.peek(), None | Some("->")) {
+            inputs.push(parser.parse_type()?);
+            match parser.peek() {
+                Some(",") => parser.pos += 1,
+                None | Some("->") => (),
+                Some(t) => bail!("expected ',' or '->', found {t:?}"),
+            }
+        }
+        let mut output = None;
+        if parser.next() == Some("->") && parser.peek().is_some() {
+            output = Some(parser.parse_type()?);
+        }
+        if let Some(t) = parser.peek() {
+            bail!("unexpected {t:?} after the return type");
+        }
+        Ok(Self { inputs, output })
+    }
+}
+
+fn lex(s: &str) -> anyhow::Result<Vec<String>> {
+    let mut tokens = vec![];
+    let mut chars = s.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        match c {
+            c if c.is_whitespace() => (),
+            '-' if chars.next_if(|(_, c)| *c == '>').is_some() => tokens.push("->".to_owned()),
+            '&' | '<' | '>' | ',' | '(' | ')' | '[' | ']' | ';' => tokens.push(c.to_string()),
+            c if c.is_alphanumeric() || c == '_' || c == ':' => {
+                let mut end = start + c.len_utf8();
+                while let Some((i, c)) =
+                    chars.next_if(|(_, c)| c.is_alphanumeric() || *c == '_' || *c == ':')
+                {
+                    end = i + c.len_utf8();
+                }
+                tokens.push(s[start..end].to_owned());
+            }
+            _ => bail!("unexpected {c:?}"),
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<String>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(String::as_str)
+    }
+
+    fn next(&mut self) -> Option<&str> {
+        self.pos += 1;
+        self.tokens.get(self.pos - 1).map(String::as_str)
+    }
+
+    fn expect(&mut self, token: &str) -> anyhow::Result<()> {
+        match self.next() {
+            Some(t) if t == token => Ok(()),
+            Some(t) => bail!("expected {token:?}, found {t:?}"),
+            None => bail!("expected {token:?}"),
+        }
+    }
+
+    /// Parse a comma-separated list of types up to a closing token.
+    fn parse_list(&mut self, close: &str) -> anyhow::Result<Vec<TypePattern>> {
+        let mut types = vec![];
+        while self.peek() != Some(close) {
+            types.push(self.parse_type()?);
+            if self.peek() == Some(",") {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+        self.expect(close)?;
+        Ok(types)
+    }
+
+    fn parse_type(&mut self) -> anyhow::Result<TypePattern> {
+        let token = self
+            .next()
+            .ok_or_else(|| anyhow!("expected a type"))?
+            .to_owned();
+        match token.as_str() {
+            "&" => {
+                let mutable = self.peek() == Some("mut");
+                if mutable {
+                    self.pos += 1;
+                }
+                Ok(TypePattern::Ref {
+                    mutable,
+                    inner: Box::new(self.parse_type()?),
+                })
+            }
+            "(" => Ok(TypePattern::Tuple(self.parse_list(")")?)),
+            "[" => {
+                let inner = self.parse_type()?;
+                // The length of an array doesn't matter.
+                if self.peek() == Some(";") {
+                    self.pos += 2;
+                }
+                self.expect("]")?;
+                Ok(TypePattern::Slice(Box::new(inner)))
+            }
+            "_" => Ok(TypePattern::Any),
+            "dyn" | "impl" => self.parse_type(),
+            t if t.starts_with(|c: char| c.is_alphanumeric() || c == ':') => {
+                let name = t.rsplit("::").next().unwrap().to_owned();
+                if name.is_empty() {
+                    bail!("expected a type, found {t:?}");
+                }
+                let mut args = vec![];
+                if self.peek() == Some("<") {
+                    self.pos += 1;
+                    args = self.parse_list(">")?;
+                }
+                if args.is_empty() && name.len() == 1 && name.starts_with(char::is_uppercase) {
+                    Ok(TypePattern::Var(name))
+                } else {
+                    Ok(TypePattern::Named { name, args })
+                }
+            }
+            t => bail!("expected a type, found {t:?}"),
+        }
+    }
+}
+
+/// Find the functions and methods in all the loaded crates matching the query, closest first.
+pub fn search<'a>(analysis: &'a Analysis, query: &SignatureQuery) -> Vec<SignatureMatch<'a>> {
+    let mut matches = vec![];
+    for (name, crate_) in &analysis.crates {
+        let root = ItemId::crate_root(CrateId { name });
+        for (id, item) in &crate_.index {
+            let rustdoc_types::ItemEnum::Function(f) = &item.inner else {
+                continue;
+            };
+            if item.crate_id != 0 {
+                continue;
+            }
+            let id = root.crate_sibling(id);
+            let self_ty = analysis
+                .parent(&id)
+                .and_then(|parent| analysis.get_item(parent))
+                .and_then(|(_, parent)| match parent {
+                    Item::Item(rustdoc_types::Item {
+                        inner: rustdoc_types::ItemEnum::Impl(i),
+                        ..
+                    }) => Some(&i.for_),
+                    _ => None,
+                });
+            let Some(cost) = match_signature(query, &f.sig, self_ty) else {
+                continue;
+            };
+            let fn_name = item.name.as_deref().unwrap_or_default();
+            matches.push(SignatureMatch {
+                path: analysis.full_name(&id, item),
+                signature: fn_signature(fn_name, f),
+                id,
+                item,
+                cost,
+            });
+        }
+    }
+    matches.sort_by(|a, b| a.cost.cmp(&b.cost).then_with(|| a.path.cmp(&b.path)));
+    matches
+}
+
+/// What the type variables of a query, like `T`, stood for where they first matched. Later
+/// occurrences must be the same type.
+type Bindings<'p, 't> = HashMap<&'p str, &'t rustdoc_types::Type>;
+
+/// How closely a function signature matches, if at all. `self_ty` is what `Self` is, for methods.
+fn match_signature(
+    query: &SignatureQuery,
+    sig: &rustdoc_types::FunctionSignature,
+    self_ty: Option<&rustdoc_types::Type>,
+) -> Option<u32> {
+    if query.inputs.len() != sig.inputs.len() {
+        return None;
+    }
+    // The return type may match either itself or what it wraps, binding type variables
+    // differently, so try the arguments with each.
+    let outputs = match (&query.output, &sig.output) {
+        (None, _) => vec![(0, Bindings::new())],
+        (Some(TypePattern::Any), _) => vec![(0, Bindings::new())],
+        (Some(TypePattern::Tuple(unit)), None) if unit.is_empty() => vec![(0, Bindings::new())],
+        (Some(_), None) => return None,
+        (Some(pattern), Some(ty)) => {
+            let mut bindings = Bindings::new();
+            let direct =
+                match_type(pattern, ty, self_ty, &mut bindings).map(|cost| (cost, bindings));
+            let mut bindings = Bindings::new();
+            let wrapped = match_wrapped(pattern, ty, self_ty, &mut bindings)
+                .map(|cost| (cost + WRAPPED_COST, bindings));
+            direct.into_iter().chain(wrapped).collect()
+        }
+    };
+
+    let types = sig.inputs.iter().map(|(_, ty)| ty).collect::<Vec<_>>();
+    outputs
+        .into_iter()
+        .filter_map(|(output_cost, bindings)| {
+            Some(output_cost + match_inputs(&query.inputs, &types, self_ty, &bindings)?)
+        })
+        .min()
+}
+
+/// How closely the arguments match, in order or otherwise, given what the type variables are
+/// bound to so far.
+fn match_inputs<'p, 't>(
+    patterns: &'p [TypePattern],
+    types: &[&'t rustdoc_types::Type],
+    self_ty: Option<&'t rustdoc_types::Type>,
+    bindings: &Bindings<'p, 't>,
+) -> Option<u32> {
+    let mut in_order_bindings = bindings.clone();
+    let in_order = patterns
+        .iter()
+        .zip(types)
+        .map(|(pattern, ty)| match_type(pattern, ty, self_ty, &mut in_order_bindings))
+        .sum::<Option<u32>>();
+    match in_order {
+        Some(cost) => Some(cost),
+        None if types.len() <= MAX_REORDERED_ARGS => {
+            let mut order = (0..types.len()).collect::<Vec<_>>();
+            Some(best_order(patterns, types, self_ty, bindings, &mut order, 0)? + REORDER_COST)
+        }
+        None => None,
+    }
+}
+
+/// The lowest cost of matching the patterns against the types in any order, trying each order of
+/// `order[k..]`.
+fn best_order<'p, 't>(
+    patterns: &'p [TypePattern],
+    types: &[&'t rustdoc_types::Type],
+    self_ty: Option<&'t rustdoc_types::Type>,
+    bindings: &Bindings<'p, 't>,
+    order: &mut [usize],
+    k: usize,
+) -> Option<u32> {
+    if k == order.len() {
+        let mut bindings = bindings.clone();
+        return order
+            .iter()
+            .enumerate()
+            .map(|(pattern_idx, &type_idx)| {
+                match_type(
+                    &patterns[pattern_idx],
+                    types[type_idx],
+                    self_ty,
+                    &mut bindings,
+                )
+            })
+            .sum();
+    }
+    let mut best = None;
+    for i in k..order.len() {
+        order.swap(k, i);
+        if let Some(cost) = best_order(patterns, types, self_ty, bindings, order, k + 1) {
+            best = Some(best.map_or(cost, |b: u32| b.min(cost)));
+        }
+        order.swap(k, i);
+    }
+    best
+}
+
+/// How closely a type matches a pattern, if at all.
+fn match_type<'p, 't>(
+    pattern: &'p TypePattern,
+    ty: &'t rustdoc_types::Type,
+    self_ty: Option<&'t rustdoc_types::Type>,
+    bindings: &mut Bindings<'p, 't>,
+) -> Option<u32> {
+    use rustdoc_types::Type;
+    match (pattern, ty) {
+        (TypePattern::Any, _) => Some(0),
+        (_, Type::Generic(name)) if name == "Self" && self_ty.is_some() => {
+            match_type(pattern, self_ty.unwrap(), None, bindings)
+        }
+        (TypePattern::Var(var), _) => match bindings.get(var.as_str()) {
+            Some(bound) => (*bound == ty).then_some(VAR_COST),
+            None => {
+                bindings.insert(var, ty);
+                Some(VAR_COST)
+            }
+        },
+        (_, Type::Generic(_) | Type::ImplTrait(_) | Type::QualifiedPath { .. } | Type::Infer) => {
+            Some(GENERIC_COST)
+        }
+        (
+            TypePattern::Ref { mutable, inner },
+            Type::BorrowedRef {
+                is_mutable, type_, ..
+            },
+        ) => {
+            if *mutable && !is_mutable {
+                return None;
+            }
+            let cost = match_type(inner, type_, self_ty, bindings)?;
+            Some(if mutable == is_mutable {
+                cost
+            } else {
+                cost + REF_COST
+            })
+        }
+        (TypePattern::Ref { .. }, _) => None,
+        // Leave off the `&` to match references too.
+        (_, Type::BorrowedRef { type_, .. }) => {
+            Some(match_type(pattern, type_, self_ty, bindings)? + REF_COST)
+        }
+        (TypePattern::Named { name, args }, Type::ResolvedPath(path)) => {
+            if path.path.rsplit("::").next() != Some(name.as_str()) {
+                return None;
+            }
+            let type_args = type_args(path.args.as_deref());
+            if args.len() > type_args.len() {
+                return None;
+            }
+            args.iter()
+                .zip(type_args)
+                .map(|(pattern, ty)| match_arg(pattern, ty, self_ty, bindings))
+                .sum()
+        }
+        (TypePattern::Named { name, args }, Type::Primitive(p)) => {
+            (name == p && args.is_empty()).then_some(0)
+        }
+        (TypePattern::Named { name, .. }, Type::DynTrait(dt)) => dt
+            .traits
+            .iter()
+            .any(|t| t.trait_.path.rsplit("::").next() == Some(name.as_str()))
+            .then_some(DYN_COST),
+        (TypePattern::Slice(inner), Type::Slice(ty)) => match_arg(inner, ty, self_ty, bindings),
+        (TypePattern::Slice(inner), Type::Array { type_, .. }) => {
+            Some(match_arg(inner, type_, self_ty, bindings)? + ARRAY_COST)
+        }
+        (TypePattern::Tuple(patterns), Type::Tuple(types)) if patterns.len() == types.len() => {
+            patterns
+                .iter()
+                .zip(types)
+                .map(|(pattern, ty)| match_type(pattern, ty, self_ty, bindings))
+                .sum()
+        }
+        _ => None,
+    }
+}
+
+/// Match a type inside another one, like the type of a slice's elements or a generic argument.
+fn match_arg<'p, 't>(
+    pattern: &'p TypePattern,
+    ty: &'t rustdoc_types::Type,
+    self_ty: Option<&'t rustdoc_types::Type>,
+    bindings: &mut Bindings<'p, 't>,
+) -> Option<u32> {
+    match (pattern, ty) {
+        (TypePattern::Any, _) => Some(0),
+        (_, rustdoc_types::Type::Generic(name)) if name != "Self" => Some(GENERIC_ARG_COST),
+        _ => match_type(pattern, ty, self_ty, bindings),
+    }
+}
+
+/// Match a type wrapped in an `Option` or `Result`, for looking for the things functions return.
+fn match_wrapped<'p, 't>(
+    pattern: &'p TypePattern,
+    ty: &'t rustdoc_types::Type,
+    self_ty: Option<&'t rustdoc_types::Type>,
+    bindings: &mut Bindings<'p, 't>,
+) -> Option<u32> {
+    let rustdoc_types::Type::ResolvedPath(path) = ty else {
+        return None;
+    };
+    if !matches!(path.path.rsplit("::").next(), Some("Option" | "Result")) {
+        return None;
+    }
+    match_arg(
+        pattern,
+        type_args(path.args.as_deref()).first()?,
+        self_ty,
+        bindings,
+    )
+}
+
+fn type_args(args: Option<&rustdoc_types::GenericArgs>) -> Vec<&rustdoc_types::Type> {
+    match args {
+        Some(rustdoc_types::GenericArgs::AngleBracketed { args, .. }) => args
+            .iter()
+            .filter_map(|arg| match arg {
+                rustdoc_types::GenericArg::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn named(name: &str, args: Vec<TypePattern>) -> TypePattern {
+        TypePattern::Named {
+            name: name.to_owned(),
+            args,
+        }
+    }
+
+    fn path(name: &str, args: Vec<rustdoc_types::Type>) -> rustdoc_types::Type {
+        rustdoc_types::Type::ResolvedPath(rustdoc_types::Path {
+            path: name.to_owned(),
+            id: rustdoc_types::Id(0),
+            args: Some(Box::new(rustdoc_types::GenericArgs::AngleBracketed {
+                args: args
+                    .into_iter()
+                    .map(rustdoc_types::GenericArg::Type)
+                    .collect(),
+                constraints: vec![],
+            })),
+        })
+    }
+
+    #[test]
+    fn var_test() {
+        let query = "Vec<T> -> T".parse::<SignatureQuery>().unwrap();
+        let u8_ty = rustdoc_types::Type::Primitive("u8".to_owned());
+        let sig = |output| rustdoc_types::FunctionSignature {
+            inputs: vec![("v".to_owned(), path("Vec", vec![u8_ty.clone()]))],
+            output: Some(output),
+            is_c_variadic: false,
+        };
+        assert_eq!(match_signature(&query, &sig(u8_ty.clone()), None), Some(2));
+        // `T` has to be the same type everywhere.
+        assert_eq!(
+            match_signature(&query, &sig(path("String", vec![])), None),
+            None
+        );
+        assert_eq!(
+            match_signature(&query, &sig(path("Option", vec![u8_ty.clone()])), None),
+            Some(2 + WRAPPED_COST)
+        );
+    }
+
+    #[test]
+    fn parse_test() {
+        let query = "&str, &mut [u8; 4] -> Result<_, io::Error>"
+            .parse::<SignatureQuery>()
+            .unwrap();
+        assert_eq!(
+            query,
+            SignatureQuery {
+                inputs: vec![
+                    TypePattern::Ref {
+                        mutable: false,
+                        inner: Box::new(named("str", vec![])),
+                    },
+                    TypePattern::Ref {
+                        mutable: true,
+                        inner: Box::new(TypePattern::Slice(Box::new(named("u8", vec![])))),
+                    },
+                ],
+                output: Some(named(
+                    "Result",
+                    vec![TypePattern::Any, named("Error", vec![])]
+                )),
+            }
+        );
+
+        let query = "Vec<T> ->".parse::<SignatureQuery>().unwrap();
+        assert_eq!(
+            query.inputs,
+            vec![named("Vec", vec![TypePattern::Var("T".to_owned())])]
+        );
+        assert_eq!(query.output, None);
+
+        let query = "-> ()".parse::<SignatureQuery>().unwrap();
+        assert_eq!(query.inputs, vec![]);
+        assert_eq!(query.output, Some(TypePattern::Tuple(vec![])));
+
+        assert!("&str -> Result<".parse::<SignatureQuery>().is_err());
+        assert!("&str -> u8 u8".parse::<SignatureQuery>().is_err());
+        assert!("u8 $".parse::<SignatureQuery>().is_err());
+    }
+}
@@ -1,14 +1,24 @@
-use crate::browser_trait::Browser;
+use crate::browser_trait::{Browser, Format, Graph, SearchResult};
 use crate::scroll_pad::ScrollPad;
 use cursive::event::Key;
+use cursive::theme::Effect;
 use cursive::traits::*;
-use cursive::views::{Dialog, LinearLayout, ScrollView, SelectView, TextView};
+use cursive::utils::markup::StyledString;
+use cursive::views::{
+    Button, Checkbox, Dialog, EditView, LinearLayout, ListView, ScrollView, SelectView, TextView,
+};
 use cursive::{Cursive, CursiveExt, XY};
 use std::borrow::Cow;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
 
 /// How many lines to scroll to before a definition.
 const SOURCE_LEADING_CONTEXT_LINES: usize = 5;
 
+/// How many steps away from the selected item graphs go by default.
+const DEFAULT_GRAPH_DEPTH: usize = 2;
+
 struct UserData<T> {
     browser: T,
 }
@@ -45,10 +55,14 @@ fn info_dialog<B: Browser + 'static>(ui: &mut Cursive, item: &B::Item) {
     let info_txt = data.browser.get_info(item);
     let (source_txt, start_line) = data.browser.get_source(item);
 
+    let has_graph = data.browser.type_graph(item, 0).is_some();
+    let links = doc_links_view::<B>(data.browser.doc_links(item));
+
     let item_dlg = item.clone();
-    let info_dialog = Dialog::around(
+    let mut info_dialog = Dialog::around(
         LinearLayout::vertical()
             .child(TextView::new(info_txt).scrollable())
+            .child(links)
             .child(
                 TextView::new(source_txt)
                     .scrollable()
@@ -64,6 +78,11 @@ fn info_dialog<B: Browser + 'static>(ui: &mut Cursive, item: &B::Item) {
         ui.add_layer(dbg_dialog);
     });
 
+    if has_graph {
+        let item = item.clone();
+        info_dialog.add_button("graph", move |ui| graph_dialog::<B>(ui, &item));
+    }
+
     ui.add_layer(info_dialog);
 
     if let Some(start_line) = start_line {
@@ -79,6 +98,116 @@ fn info_dialog<B: Browser + 'static>(ui: &mut Cursive, item: &B::Item) {
     }
 }
 
+/// Lists the intra-doc links from an item's docs. Tab moves between them, and Enter on one closes
+/// the info dialog and goes to the item it links to.
+fn doc_links_view<B: Browser + 'static>(links: Vec<(String, Option<B::ItemId>)>) -> LinearLayout {
+    let mut view = LinearLayout::vertical();
+    if links.is_empty() {
+        return view;
+    }
+    view.add_child(TextView::new("links:"));
+    for (text, target) in links {
+        match target {
+            Some(target) => view.add_child(Button::new_raw(format!("→ {text}"), move |ui| {
+                let data = ui.user_data::<UserData<B>>().unwrap();
+                match data.browser.item_path(&target) {
+                    Some(path) => {
+                        ui.pop_layer();
+                        go_to::<B>(ui, &path);
+                    }
+                    None => ui.add_layer(Dialog::info("can't find where this item is listed")),
+                }
+            })),
+            None => view.add_child(TextView::new(format!("✗ {text} (unresolved)"))),
+        }
+    }
+    view
+}
+
+fn graph_dialog<B: Browser + 'static>(ui: &mut Cursive, item: &B::Item) {
+    let mut dialog = Dialog::around(
+        LinearLayout::horizontal()
+            .child(TextView::new("depth: "))
+            .child(
+                EditView::new()
+                    .content(DEFAULT_GRAPH_DEPTH.to_string())
+                    .with_name("graph_depth")
+                    .fixed_width(5),
+            ),
+    )
+    .title("export type graph");
+    for format in [Format::Dot, Format::Mermaid] {
+        let item = item.clone();
+        dialog.add_button(format.name(), move |ui| {
+            export_graph::<B>(ui, &item, format);
+        });
+    }
+    ui.add_layer(dialog.dismiss_button("cancel"));
+}
+
+fn export_graph<B: Browser + 'static>(ui: &mut Cursive, item: &B::Item, format: Format) {
+    let depth = ui
+        .call_on_name("graph_depth", |view: &mut EditView| {
+            view.get_content().trim().parse::<usize>().ok()
+        })
+        .flatten();
+    let Some(depth) = depth else {
+        ui.add_layer(Dialog::info("depth must be a number"));
+        return;
+    };
+
+    let data = ui.user_data::<UserData<B>>().unwrap();
+    let Some(graph) = data.browser.type_graph(item, depth) else {
+        return;
+    };
+    let name = graph.nodes[0].name.replace("::", ".");
+    save_graph::<B>(ui, &graph, &name, format);
+}
+
+fn crate_graph_dialog<B: Browser + 'static>(ui: &mut Cursive) {
+    let mut dialog = Dialog::text("Export a graph of how the loaded crates depend on each other.")
+        .title("export crate graph");
+    for format in [Format::Dot, Format::Mermaid] {
+        dialog.add_button(format.name(), move |ui| {
+            let data = ui.user_data::<UserData<B>>().unwrap();
+            if let Some(graph) = data.browser.crate_graph() {
+                save_graph::<B>(ui, &graph, "crates", format);
+            }
+        });
+    }
+    ui.add_layer(dialog.dismiss_button("cancel"));
+}
+
+/// Write a graph to graphs/{name}.{ext} in the browser's output directory, replacing the export
+/// dialog with one saying how it went.
+fn save_graph<B: Browser + 'static>(ui: &mut Cursive, graph: &Graph, name: &str, format: Format) {
+    let data = ui.user_data::<UserData<B>>().unwrap();
+    let dir = data
+        .browser
+        .output_dir()
+        .unwrap_or(Path::new(""))
+        .join("graphs");
+    let write = || -> io::Result<PathBuf> {
+        fs::create_dir_all(&dir)?;
+        let path = dir.join(format!("{name}.{}", format.extension()));
+        let mut out = BufWriter::new(File::create(&path)?);
+        graph.write(format, &mut out)?;
+        out.flush()?;
+        Ok(path)
+    };
+    let msg = match write() {
+        Ok(path) => format!(
+            "graph of {} nodes and {} edges written to {}",
+            graph.nodes.len(),
+            graph.edges.len(),
+            path.display(),
+        ),
+        Err(e) => format!("failed to write graph: {e}"),
+    };
+    ui.pop_layer();
+    ui.add_layer(Dialog::info(msg));
+}
+
 fn add_panel<B: Browser + 'static>(ui: &mut Cursive, parent_id: &B::ItemId, depth: usize) {
     ui.call_on_name("horiz_layout", |view: &mut LinearLayout| {
         while view.len() > depth {
@@ -101,11 +230,12 @@ fn add_panel<B: Browser + 'static>(ui: &mut Cursive, parent_id: &B::ItemId, dept
     let mut local_depth = depth;
     let mut local_parent = Cow::Borrowed(parent_id);
     while let Some(view) = make_selectview(data, &local_parent, local_depth) {
+        let view_depth = local_depth;
         if let Some((_label, (id, _item))) = view.get_item(0) {
             local_depth += 1;
             local_parent = Cow::Owned(id.clone());
         }
-        next.push(view);
+        next.push(view.with_name(pane_name(view_depth)));
     }
 
     if next.is_empty() {
@@ -121,6 +251,256 @@ fn add_panel<B: Browser + 'static>(ui: &mut Cursive, parent_id: &B::ItemId, dept
     });
 }
 
+fn search_docs_dialog<B: Browser + 'static>(ui: &mut Cursive) {
+    ui.add_layer(
+        Dialog::around(
+            EditView::new()
+                .on_submit(show_doc_results::<B>)
+                .with_name("doc_query")
+                .fixed_width(50),
+        )
+        .title("search docs")
+        .button("search", |ui| {
+            let query = ui
+                .call_on_name("doc_query", |view: &mut EditView| view.get_content())
+                .unwrap();
+            show_doc_results::<B>(ui, &query);
+        })
+        .dismiss_button("cancel"),
+    );
+}
+
+fn show_doc_results<B: Browser + 'static>(ui: &mut Cursive, query: &str) {
+    let data = ui.user_data::<UserData<B>>().unwrap();
+    let Some(results) = data.browser.search_docs(query) else {
+        return;
+    };
+    ui.pop_layer();
+    search_results_dialog::<B>(ui, &format!("docs matching {query:?}"), results);
+}
+
+fn search_signatures_dialog<B: Browser + 'static>(ui: &mut Cursive) {
+    ui.add_layer(
+        Dialog::around(
+            LinearLayout::vertical()
+                .child(TextView::new(
+                    "argument and return types, e.g. \"&str, usize -> Option<String>\"",
+                ))
+                .child(
+                    EditView::new()
+                        .on_submit(show_signature_results::<B>)
+                        .with_name("signature_query"),
+                ),
+        )
+        .title("search functions by type")
+        .button("search", |ui| {
+            let query = ui
+                .call_on_name("signature_query", |view: &mut EditView| view.get_content())
+                .unwrap();
+            show_signature_results::<B>(ui, &query);
+        })
+        .dismiss_button("cancel")
+        .fixed_width(60),
+    );
+}
+
+fn show_signature_results<B: Browser + 'static>(ui: &mut Cursive, query: &str) {
+    let data = ui.user_data::<UserData<B>>().unwrap();
+    match data.browser.search_signatures(query) {
+        Some(Ok(results)) => {
+            ui.pop_layer();
+            search_results_dialog::<B>(ui, &format!("functions matching {query:?}"), results);
+        }
+        Some(Err(e)) => ui.add_layer(Dialog::info(format!("{e:#}"))),
+        None => (),
+    }
+}
+
+/// Lists items found by a search, with why each matched. Enter on one goes to it.
+fn search_results_dialog<B: Browser + 'static>(
+    ui: &mut Cursive,
+    title: &str,
+    results: Vec<SearchResult<B::ItemId>>,
+) {
+    if results.is_empty() {
+        ui.add_layer(Dialog::info(format!("no {title}")));
+        return;
+    }
+    let count = results.len();
+    ui.add_layer(
+        Dialog::around(results_view::<B>(results).scrollable())
+            .title(format!("{count} {title}"))
+            .dismiss_button("close"),
+    );
+}
+
+/// A list of items found by a search or query, with why each matched, where enter goes to the
+/// selected item.
+fn results_view<B: Browser + 'static>(
+    results: Vec<SearchResult<B::ItemId>>,
+) -> SelectView<B::ItemId> {
+    let mut select = SelectView::new();
+    for result in results {
+        let mut label = StyledString::plain(format!("{}  ", result.label));
+        match result.highlight {
+            Some(range) => {
+                label.append_plain(&result.detail[..range.start]);
+                label.append_styled(&result.detail[range.clone()], Effect::Reverse);
+                label.append_plain(&result.detail[range.end..]);
+            }
+            None => label.append_plain(&result.detail),
+        }
+        select.add_item(label, result.id);
+    }
+    select.set_on_submit(|ui, id| {
+        let data = ui.user_data::<UserData<B>>().unwrap();
+        match data.browser.item_path(id) {
+            Some(path) => {
+                ui.pop_layer();
+                go_to::<B>(ui, &path);
+            }
+            None => ui.add_layer(Dialog::info("can't find where this item is listed")),
+        }
+    });
+    select
+}
+
+fn query_impl_dialog<B: Browser + 'static>(ui: &mut Cursive) {
+    ui.add_layer(
+        Dialog::around(
+            LinearLayout::vertical()
+                .child(TextView::new(
+                    "type, e.g. \"Vec\" or \"std::collections::HashMap\"",
+                ))
+                .child(EditView::new().with_name("impl_type"))
+                .child(TextView::new("trait, e.g. \"Clone\""))
+                .child(
+                    EditView::new()
+                        .on_submit(|ui, _| show_impl_answer::<B>(ui))
+                        .with_name("impl_trait"),
+                ),
+        )
+        .title("does a type implement a trait?")
+        .button("check", show_impl_answer::<B>)
+        .dismiss_button("cancel")
+        .fixed_width(60),
+    );
+}
+
+fn show_impl_answer<B: Browser + 'static>(ui: &mut Cursive) {
+    let content = |ui: &mut Cursive, name| {
+        ui.call_on_name(name, |view: &mut EditView| view.get_content())
+            .unwrap()
+    };
+    let type_path = content(ui, "impl_type");
+    let trait_path = content(ui, "impl_trait");
+    let data = ui.user_data::<UserData<B>>().unwrap();
+    match data.browser.query_impl(&type_path, &trait_path) {
+        Some(Ok(answer)) => {
+            let mut layout = LinearLayout::vertical().child(TextView::new(answer.summary));
+            if !answer.impls.is_empty() {
+                layout.add_child(TextView::new(" "));
+                layout.add_child(results_view::<B>(answer.impls).scrollable());
+            }
+            ui.pop_layer();
+            ui.add_layer(
+                Dialog::around(layout)
+                    .title(format!("{type_path}: {trait_path}"))
+                    .dismiss_button("close"),
+            );
+        }
+        Some(Err(e)) => ui.add_layer(Dialog::info(format!("{e:#}"))),
+        None => (),
+    }
+}
+
+/// Select the given items in each pane in turn, starting with the crate in the leftmost pane, as if
+/// the user had browsed to them. Stops at the first one that isn't listed, and focuses the pane
+/// of the last one that was.
+fn go_to<B: Browser + 'static>(ui: &mut Cursive, path: &[B::ItemId]) {
+    let Some((crate_id, rest)) = path.split_first() else {
+        return;
+    };
+    let found = ui
+        .call_on_name("crates_select", |view: &mut SelectView<B::ItemId>| {
+            let idx = view.iter().position(|(_label, id)| id == crate_id)?;
+            // This returns the on_select callback, which is called below instead.
+            let _ = view.set_selection(idx);
+            Some(())
+        })
+        .flatten();
+    if found.is_none() {
+        return;
+    }
+    add_panel::<B>(ui, crate_id, 1);
+
+    let mut focus = 0;
+    for (depth, id) in (1..).zip(rest) {
+        let found = ui
+            .call_on_name(
+                &pane_name(depth),
+                |view: &mut SelectView<(B::ItemId, B::Item)>| {
+                    let idx = view
+                        .iter()
+                        .position(|(_label, (item_id, _))| item_id == id)?;
+                    let _ = view.set_selection(idx);
+                    Some(())
+                },
+            )
+            .flatten();
+        if found.is_none() {
+            break;
+        }
+        focus = depth;
+        add_panel::<B>(ui, id, depth + 1);
+    }
+
+    ui.call_on_name("horiz_layout", |view: &mut LinearLayout| {
+        let _ = view.set_focus_index(focus);
+    });
+}
+
+/// Name of the selectview in the pane at the given depth (the crates being at depth 0).
+fn pane_name(depth: usize) -> String {
+    format!("pane_{depth}")
+}
+
+/// Returns the ID of the crate currently selected in the leftmost pane.
+fn selected_crate<B: Browser + 'static>(ui: &mut Cursive) -> Option<B::ItemId> {
+    ui.call_on_name("crates_select", |view: &mut SelectView<B::ItemId>| {
+        view.selection().map(|id| (*id).clone())
+    })
+    .flatten()
+}
+
+fn view_options_dialog<B: Browser + 'static>(ui: &mut Cursive) {
+    let Some(crate_id) = selected_crate::<B>(ui) else {
+        return;
+    };
+
+    let data = ui.user_data::<UserData<B>>().unwrap();
+    let mut list = ListView::new();
+    for (option, enabled) in data.browser.list_view_options(&crate_id) {
+        let label = format!("show {option}");
+        let crate_id = crate_id.clone();
+        let checkbox = Checkbox::new()
+            .with_checked(enabled)
+            .on_change(move |ui, checked| {
+                let data = ui.user_data::<UserData<B>>().unwrap();
+                data.browser.set_view_option(&crate_id, &option, checked);
+                // Re-list everything with the new options.
+                add_panel::<B>(ui, &crate_id, 1);
+            });
+        list.add_child(label, checkbox);
+    }
+
+    ui.add_layer(
+        Dialog::around(list)
+            .title("view options for this crate")
+            .dismiss_button("ok"),
+    );
+}
+
 fn about(ui: &mut Cursive) {
     ui.add_layer(
         Dialog::around(
@@ -148,6 +528,23 @@ pub fn run<B: Browser + 'static>(browser: B) {
     ui.menubar()
         .add_leaf("rsbrowse!", about)
         .add_delimiter()
+        .add_leaf("View", view_options_dialog::<B>);
+    if browser.supports_doc_search() {
+        ui.menubar()
+            .add_leaf("Search docs", search_docs_dialog::<B>);
+    }
+    if browser.supports_signature_search() {
+        ui.menubar()
+            .add_leaf("Search types", search_signatures_dialog::<B>);
+    }
+    if browser.supports_impl_query() {
+        ui.menubar().add_leaf("Implements?", query_impl_dialog::<B>);
+    }
+    if browser.supports_crate_graph() {
+        ui.menubar()
+            .add_leaf("Crate graph", crate_graph_dialog::<B>);
+    }
+    ui.menubar()
         .add_leaf("Quit", |ui| ui.quit())
         .add_leaf("(ESC to activate menu)", |_| ());
     ui.set_autohide_menu(false);
@@ -187,7 +584,7 @@ pub fn run<B: Browser + 'static>(browser: B) {
         ScrollView::new(
             LinearLayout::horizontal()
                 .child(ScrollPad::new(
-                    ScrollView::new(crates_select).scroll_y(true),
+                    ScrollView::new(crates_select.with_name("crates_select")).scroll_y(true),
                 ))
                 .with_name("horiz_layout"),
         )
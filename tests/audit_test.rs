@@ -0,0 +1,66 @@
+#[macro_use]
+extern crate lazy_static;
+
+mod common;
+
+use common::ANALYSIS;
+use rsbrowse::audit::{self, UnsafeKind};
+
+#[test]
+fn unsafe_items() {
+    let items = audit::unsafe_items(&ANALYSIS, "testcrate");
+    let found = items
+        .iter()
+        .map(|item| (item.module.as_str(), item.kind, item.label.as_str()))
+        .collect::<Vec<_>>();
+    let module = "testcrate::unsafe_code";
+    assert_eq!(
+        found,
+        vec![
+            (module, UnsafeKind::ExternFunction, "extern fn abs"),
+            (module, UnsafeKind::ExternStatic, "extern static environ"),
+            (module, UnsafeKind::StaticMut, "static mut COUNTER"),
+            (module, UnsafeKind::Function, "unsafe fn read"),
+            // rustdoc doesn't mark these as unsafe; they're found through the traits.
+            (module, UnsafeKind::Impl, "unsafe impl Send for Raw"),
+            (module, UnsafeKind::Impl, "unsafe impl Zeroable for Raw"),
+            (module, UnsafeKind::Trait, "unsafe trait Zeroable"),
+        ]
+    );
+}
+
+#[test]
+fn index() {
+    let index = audit::UnsafeIndex::build(&ANALYSIS);
+    assert_eq!(index.count("testcrate"), 7);
+    let labels = |items: Vec<audit::UnsafeItem>| {
+        items.into_iter().map(|item| item.label).collect::<Vec<_>>()
+    };
+    assert_eq!(
+        labels(index.unsafe_items(&ANALYSIS, "testcrate")),
+        labels(audit::unsafe_items(&ANALYSIS, "testcrate"))
+    );
+    // Only workspace crates are indexed.
+    assert_eq!(index.count("externcrate"), 0);
+}
+
+#[test]
+fn report() {
+    let mut out = vec![];
+    let count = audit::write_report(&ANALYSIS, &mut out).unwrap();
+    let report = String::from_utf8(out).unwrap();
+    assert_eq!(count, 7);
+    assert!(
+        report.starts_with(
+            "testcrate\n  \
+            testcrate::unsafe_code\n    \
+            extern fn abs (src/lib.rs:"
+        ),
+        "{report}"
+    );
+    assert!(
+        report.contains("\n    unsafe fn read (src/lib.rs:"),
+        "{report}"
+    );
+    assert!(report.ends_with("\n7 unsafe items\n"), "{report}");
+}
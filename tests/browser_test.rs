@@ -4,27 +4,32 @@ extern crate lazy_static;
 use rsbrowse::analysis::{self, Analysis, Item};
 use rsbrowse::browser_rustdoc::RustdocBrowser;
 use rsbrowse::browser_trait::Browser;
+use rsbrowse::layout::TypeLayouts;
 use std::path::Path;
 
 lazy_static! {
-    static ref BROWSER_STATIC: RustdocBrowser = {
-        let path = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/testcrate"));
-
-        let status = std::process::Command::new("cargo")
-            .arg("clean")
-            .current_dir(&path)
-            .status()
-            .expect("Failed to run 'cargo clean' on test crate");
-        if !status.success() {
-            panic!("Failed to run 'cargo clean' on test crate");
-        }
-
-        Analysis::generate(&path, Some("nightly")).expect("Failed to generate analysis data.");
-        RustdocBrowser::new(Analysis::load(&path).expect("Failed to load analysis"))
-    };
+    static ref BROWSER_STATIC: RustdocBrowser = generate_browser();
     static ref BROWSER: &'static RustdocBrowser = &BROWSER_STATIC;
 }
 
+// Lint attributes on a `lazy_static!` are ignored, so the setup is here.
+#[allow(clippy::needless_borrows_for_generic_args)]
+fn generate_browser() -> RustdocBrowser {
+    let path = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/testcrate"));
+
+    let status = std::process::Command::new("cargo")
+        .arg("clean")
+        .current_dir(&path)
+        .status()
+        .expect("Failed to run 'cargo clean' on test crate");
+    if !status.success() {
+        panic!("Failed to run 'cargo clean' on test crate");
+    }
+
+    Analysis::generate(&path, Some("nightly")).expect("Failed to generate analysis data.");
+    RustdocBrowser::new(Analysis::load(&path).expect("Failed to load analysis"))
+}
+
 fn iter_labels<T>(items: &[(String, T)]) -> impl Iterator<Item = &str> {
     items.iter().map(|(label, _)| label.as_str())
 }
@@ -32,7 +37,9 @@ fn iter_labels<T>(items: &[(String, T)]) -> impl Iterator<Item = &str> {
 trait VecExt<'a, T> {
     fn contains_label(&self, s: &str) -> bool;
     fn by_label(&'a self, s: &str) -> &'a T;
+    fn by_prefix(&'a self, s: &str) -> &'a T;
     fn labels(&self) -> Vec<String>;
+    fn labels_without_std_counts(&self) -> Vec<String>;
 }
 
 impl<'a, T> VecExt<'a, T> for Vec<(String, T)> {
@@ -46,9 +53,29 @@ impl<'a, T> VecExt<'a, T> for Vec<(String, T)> {
             .expect("not found")
             .1
     }
+    fn by_prefix(&'a self, s: &str) -> &'a T {
+        &self
+            .iter()
+            .find(|(label, _)| label.starts_with(s))
+            .expect("not found")
+            .1
+    }
     fn labels(&self) -> Vec<String> {
         iter_labels(self).map(|s| s.to_owned()).collect()
     }
+    /// The labels, without the counts that depend on the toolchain's standard library: blanket
+    /// impls, and the methods they bring.
+    fn labels_without_std_counts(&self) -> Vec<String> {
+        iter_labels(self)
+            .map(|s| {
+                ["blanket impls", "methods"]
+                    .into_iter()
+                    .find(|row| s.starts_with(&format!("{row} (")))
+                    .unwrap_or(s)
+            })
+            .map(|s| s.to_owned())
+            .collect()
+    }
 }
 
 fn items_eq(a: &Item, b: &Item) -> bool {
@@ -74,6 +101,15 @@ impl<'a> ItemExt for Item<'a> {
     }
 }
 
+/// The standard library crates are dependencies too, if they're loaded.
+fn dependencies_label() -> &'static str {
+    if analysis::get_stdlib_analysis_path(Some("nightly")).is_ok() {
+        "dependencies (4)"
+    } else {
+        "dependencies (1)"
+    }
+}
+
 #[test]
 fn list_items() {
     let has_stdlib = analysis::get_stdlib_analysis_path(Some("nightly")).is_ok();
@@ -88,13 +124,14 @@ fn list_items() {
                 "anyhow",
                 "core",
                 "externcrate",
+                "macrocrate",
                 "proc_macro",
                 "std",
                 "test",
                 "testcrate",
             ][..]
         } else {
-            &["anyhow", "externcrate", "testcrate"][..]
+            &["anyhow", "externcrate", "macrocrate", "testcrate"][..]
         },
     );
 
@@ -102,75 +139,159 @@ fn list_items() {
 
     // Pane 1
 
+    #[allow(clippy::needless_borrow)]
     let root_items = BROWSER.list_items(&crate_id);
     assert_eq!(
         root_items.labels(),
-        &["mod x", "mod y", "mod z", "trait Trait",]
+        &[
+            "pub mod api",
+            "pub mod attrs",
+            "pub mod ctors",
+            "pub mod deref",
+            "pub mod links",
+            "pub mod lookalike",
+            "pub mod macros",
+            "pub mod pairs",
+            "pub mod sigs",
+            "pub mod traits",
+            "pub mod unsafe_code",
+            "pub mod x",
+            "pub mod y",
+            "pub mod z",
+            "pub trait Trait",
+            "macros (1)",
+            "unsafe (7)",
+            "doc coverage (6%)",
+            dependencies_label(),
+        ]
     );
 
     // Pane 2
 
-    let mod_x = root_items.by_label("mod x");
+    let mod_api = root_items.by_label("pub mod api");
+    let mod_api_items = BROWSER.list_items(&mod_api.0);
+    assert_eq!(
+        mod_api_items.labels(),
+        &[
+            "mod private",
+            "pub use externcrate::ExternTrait",
+            "pub use self::private::Reexported as Renamed",
+            "pub use self::private::inner::*",
+        ]
+    );
+
+    // Imports have the children of what they import.
+    let use_renamed = mod_api_items.by_label("pub use self::private::Reexported as Renamed");
+    let use_renamed_items = BROWSER.list_items(&use_renamed.0);
+    assert_eq!(
+        use_renamed_items.labels_without_std_counts(),
+        &[
+            "impl Self",
+            "auto traits: RefUnwindSafe ✓ Send ✓ Sync ✓ Unpin ✓ UnwindSafe ✓",
+            "blanket impls",
+            "methods",
+            "constructors (1)",
+        ]
+    );
+
+    // Glob imports list the names they import.
+    let use_glob = mod_api_items.by_label("pub use self::private::inner::*");
+    let use_glob_items = BROWSER.list_items(&use_glob.0);
+    assert_eq!(use_glob_items.labels(), &["pub fn glob_fn"]);
+
+    // Imports of items from other crates have the children of the item in its crate.
+    let use_extern = mod_api_items.by_label("pub use externcrate::ExternTrait");
+    let use_extern_items = BROWSER.list_items(&use_extern.0);
+    assert_eq!(
+        use_extern_items.labels(),
+        &["fn default_method", "fn required_method", "subtraits (1)"]
+    );
+
+    let mod_x = root_items.by_label("pub mod x");
     let mod_x_items = BROWSER.list_items(&mod_x.0);
-    assert_eq!(mod_x_items.labels(), &["enum E", "struct S"]);
+    assert_eq!(mod_x_items.labels(), &["enum E", "pub struct S"]);
 
-    let mod_y = root_items.by_label("mod y");
+    let mod_y = root_items.by_label("pub mod y");
     let mod_y_items = BROWSER.list_items(&mod_y.0);
-    assert_eq!(mod_y_items.labels(), &["struct S"]);
+    assert_eq!(mod_y_items.labels(), &["pub struct S"]);
 
-    let mod_z = root_items.by_label("mod z");
+    let mod_z = root_items.by_label("pub mod z");
     let mod_z_items = BROWSER.list_items(&mod_z.0);
-    assert_eq!(mod_z_items.labels(), &["struct S"]);
+    assert_eq!(mod_z_items.labels(), &["pub struct S"]);
 
     // Assert that the three "struct S" defs are not the same.
     assert!(!items_eq(
-        &mod_x_items.by_label("struct S").1,
-        &mod_y_items.by_label("struct S").1
+        &mod_x_items.by_label("pub struct S").1,
+        &mod_y_items.by_label("pub struct S").1
     ));
     assert!(!items_eq(
-        &mod_y_items.by_label("struct S").1,
-        &mod_z_items.by_label("struct S").1
+        &mod_y_items.by_label("pub struct S").1,
+        &mod_z_items.by_label("pub struct S").1
     ));
 
-    let trait_trait = root_items.by_label("trait Trait");
+    let trait_trait = root_items.by_label("pub trait Trait");
     let trait_items = BROWSER.list_items(&trait_trait.0);
-    assert_eq!(trait_items.labels(), &["fn method"]);
+    assert_eq!(trait_items.labels(), &["fn method", "generics (1)"]);
 
     // Pane 3
 
     let x_e = mod_x_items.by_label("enum E");
     let x_e_items = BROWSER.list_items(&x_e.0);
     assert_eq!(
-        x_e_items.labels(),
+        x_e_items.labels_without_std_counts(),
         &[
             "variant StructVariant",
             "variant TupleVariant(S)",
             "variant UnitVariant",
+            "auto traits: RefUnwindSafe ✗ Send ✗ Sync ✗ Unpin ✓ UnwindSafe ✗",
+            "blanket impls",
+            "methods",
         ]
     );
 
-    let x_s = mod_x_items.by_label("struct S");
+    let x_s = mod_x_items.by_label("pub struct S");
     let x_s_items = BROWSER.list_items(&x_s.0);
     assert_eq!(
-        x_s_items.labels(),
+        x_s_items.labels_without_std_counts(),
         &[
             "fn_field: Box<dyn Fn(usize, String) -> Option<i32>>",
-            "int_field: i32",
+            "pub int_field: i32",
             "opt_field: Option<Result<i32, std::io::Error>>",
             "string_field: String",
             "impl Self",
             "impl core::fmt::Display",
             "impl externcrate::ExternTrait",
+            "auto traits: RefUnwindSafe ✗ Send ✗ Sync ✗ Unpin ✓ UnwindSafe ✗",
+            "blanket impls",
+            "methods",
+            "constructors (1)",
         ]
     );
 
-    let y_s = mod_y_items.by_label("struct S");
+    let y_s = mod_y_items.by_label("pub struct S");
     let y_s_items = BROWSER.list_items(&y_s.0);
-    assert_eq!(y_s_items.labels(), &["impl Self", "impl Trait<u64>",]);
+    assert_eq!(
+        y_s_items.labels_without_std_counts(),
+        &[
+            "impl Self",
+            "impl Trait<u64>",
+            "auto traits: RefUnwindSafe ✓ Send ✓ Sync ✓ Unpin ✓ UnwindSafe ✓",
+            "blanket impls",
+            "methods",
+        ]
+    );
 
-    let z_s = mod_z_items.by_label("struct S");
+    let z_s = mod_z_items.by_label("pub struct S");
     let z_s_items = BROWSER.list_items(&z_s.0);
-    assert_eq!(z_s_items.labels(), &["impl Trait<String>"]);
+    assert_eq!(
+        z_s_items.labels_without_std_counts(),
+        &[
+            "impl Trait<String>",
+            "auto traits: RefUnwindSafe ✓ Send ✓ Sync ✓ Unpin ✓ UnwindSafe ✓",
+            "blanket impls",
+            "methods",
+        ]
+    );
 
     // Pane 4
 
@@ -189,34 +310,852 @@ fn list_items() {
 
     let x_s_self = x_s_items.by_label("impl Self");
     let x_s_self_items = BROWSER.list_items(&x_s_self.0);
-    assert_eq!(x_s_self_items.labels(), &["fn f"]);
+    assert_eq!(x_s_self_items.labels(), &["pub fn f"]);
 
     let x_s_extern = x_s_items.by_label("impl externcrate::ExternTrait");
     let x_s_extern_items = BROWSER.list_items(&x_s_extern.0);
     assert_eq!(
         x_s_extern_items.labels(),
-        &["fn required_method", "trait ExternTrait"]
+        &[
+            "fn default_method (default)",
+            "fn required_method",
+            "pub trait ExternTrait"
+        ]
     );
 
     let y_s_self = y_s_items.by_label("impl Self");
     let y_s_self_items = BROWSER.list_items(&y_s_self.0);
-    assert_eq!(y_s_self_items.labels(), &["fn spoopadoop"]);
+    assert_eq!(y_s_self_items.labels(), &["pub fn spoopadoop"]);
 
     let y_s_trait = y_s_items.by_label("impl Trait<u64>");
     let y_s_trait_items = BROWSER.list_items(&y_s_trait.0);
     // includes "fn method" because it overrides the default in the trait:
-    assert_eq!(y_s_trait_items.labels(), &["fn method", "trait Trait"]);
+    assert_eq!(
+        y_s_trait_items.labels(),
+        &["fn method (override)", "pub trait Trait"]
+    );
 
     let z_s_trait = z_s_items.by_label("impl Trait<String>");
     let z_s_trait_items = BROWSER.list_items(&z_s_trait.0);
-    // doesn't include "fn method" because it didn't override it:
-    assert_eq!(z_s_trait_items.labels(), &["trait Trait"]);
+    // "fn method" is inherited from the trait because it didn't override it:
+    assert_eq!(
+        z_s_trait_items.labels(),
+        &["fn method (default)", "pub trait Trait"]
+    );
+
+    // The inherited method is the trait's own, with its default body.
+    let z_s_method = z_s_trait_items.by_label("fn method (default)");
+    let trait_method = trait_items.by_label("fn method");
+    assert_eq!(z_s_method.0, trait_method.0);
 
     // Pane 5
-    let x_s_self_f = x_s_self_items.by_label("fn f");
+    let x_s_self_f = x_s_self_items.by_label("pub fn f");
     let x_s_self_f_items = BROWSER.list_items(&x_s_self_f.0);
     assert_eq!(
         x_s_self_f_items.labels(),
         &["self: &Self", "e_arg: E", "-> S",]
     );
 }
+
+#[test]
+fn hide_private_items() {
+    let crates = BROWSER.list_crates();
+    let crate_id = crates.by_label("testcrate");
+    let root_items = BROWSER.list_items(crate_id);
+    let mod_x = root_items.by_label("pub mod x");
+
+    // Workspace crates show everything by default.
+    assert!(BROWSER
+        .list_view_options(crate_id)
+        .contains(&("private items".to_owned(), true)));
+
+    // Use a copy of the browser so the option doesn't affect other tests.
+    let browser = RustdocBrowser::new(
+        Analysis::load(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/testcrate"))
+            .expect("Failed to load analysis"),
+    );
+    let browser = &browser;
+    browser.set_view_option(crate_id, "private items", false);
+
+    let mod_x_items = browser.list_items(&mod_x.0);
+    assert_eq!(mod_x_items.labels(), &["pub struct S"]);
+    assert!(!mod_x_items.contains_label("enum E"));
+
+    let x_s = mod_x_items.by_label("pub struct S");
+    assert_eq!(
+        x_s.1.unwrap_item().visibility,
+        rustdoc_types::Visibility::Public
+    );
+    let x_s_items = browser.list_items(&x_s.0);
+    assert_eq!(
+        x_s_items.labels_without_std_counts(),
+        &[
+            "pub int_field: i32",
+            "impl Self",
+            "impl core::fmt::Display",
+            "impl externcrate::ExternTrait",
+            "auto traits: RefUnwindSafe ✗ Send ✗ Sync ✗ Unpin ✓ UnwindSafe ✗",
+            "blanket impls",
+            "methods",
+            "constructors (1)",
+        ]
+    );
+
+    // Fields of enum variants are as visible as the enum.
+    let mod_x_items = BROWSER.list_items(&mod_x.0);
+    let variants = browser.list_items(&mod_x_items.by_label("enum E").0);
+    let fields = browser.list_items(&variants.by_label("variant StructVariant").0);
+    assert_eq!(fields.labels(), &["a: S"]);
+}
+
+#[test]
+fn api_view() {
+    let crates = BROWSER.list_crates();
+    let crate_id = crates.by_label("testcrate");
+
+    // Use a copy of the browser so the option doesn't affect other tests.
+    let browser = RustdocBrowser::new(
+        Analysis::load(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/testcrate"))
+            .expect("Failed to load analysis"),
+    );
+    let browser = &browser;
+    browser.set_view_option(crate_id, "public API by import path", true);
+
+    let root_items = browser.list_items(crate_id);
+    assert_eq!(
+        root_items.labels(),
+        &[
+            "pub mod api",
+            "pub mod attrs",
+            "pub mod ctors",
+            "pub mod deref",
+            "pub mod links",
+            "pub mod lookalike",
+            "pub mod macros",
+            "pub mod pairs",
+            "pub mod sigs",
+            "pub mod traits",
+            "pub mod unsafe_code",
+            "pub mod x",
+            "pub mod y",
+            "pub mod z",
+            "pub trait Trait",
+            "macros (1)",
+            "unsafe (7)",
+            "doc coverage (6%)",
+            dependencies_label(),
+        ]
+    );
+
+    // The private module is collapsed, and re-exports are listed by the name they're exported as.
+    let mod_api = root_items.by_label("pub mod api");
+    let mod_api_items = browser.list_items(&mod_api.0);
+    assert_eq!(
+        mod_api_items.labels(),
+        &[
+            "pub fn glob_fn",
+            "pub struct Renamed",
+            "pub trait ExternTrait"
+        ]
+    );
+    assert_eq!(
+        mod_api_items
+            .by_label("pub struct Renamed")
+            .1
+            .unwrap_item()
+            .name
+            .as_deref(),
+        Some("Reexported")
+    );
+
+    // Private items aren't part of the API.
+    let mod_x = root_items.by_label("pub mod x");
+    let mod_x_items = browser.list_items(&mod_x.0);
+    assert_eq!(mod_x_items.labels(), &["pub struct S"]);
+}
+
+#[test]
+fn impl_groups() {
+    let crates = BROWSER.list_crates();
+    let crate_id = crates.by_label("testcrate");
+    let root_items = BROWSER.list_items(crate_id);
+    let mod_x = root_items.by_label("pub mod x");
+    let mod_x_items = BROWSER.list_items(&mod_x.0);
+    let x_s = mod_x_items.by_label("pub struct S");
+    let x_s_items = BROWSER.list_items(&x_s.0);
+
+    let auto_traits =
+        x_s_items.by_label("auto traits: RefUnwindSafe ✗ Send ✗ Sync ✗ Unpin ✓ UnwindSafe ✗");
+    assert_eq!(
+        BROWSER.list_items(&auto_traits.0).labels(),
+        &[
+            "impl !core::marker::Send",
+            "impl !core::marker::Sync",
+            "impl !core::panic::unwind_safe::RefUnwindSafe",
+            "impl !core::panic::unwind_safe::UnwindSafe",
+            "impl core::marker::Freeze",
+            "impl core::marker::Unpin",
+        ]
+    );
+
+    let blanket = x_s_items.by_prefix("blanket impls (");
+    let blanket_impls = BROWSER.list_items(&blanket.0).labels();
+    assert!(x_s_items.contains_label(&format!("blanket impls ({})", blanket_impls.len())));
+    assert!(blanket_impls.contains(&"impl Describe for T where T: Display".to_owned()));
+    assert!(blanket_impls.contains(&"impl Hidden for T".to_owned()));
+    // The workspace's doc-hidden traits are shown by default, but the standard library's aren't,
+    // when it's loaded to tell which those are.
+    if analysis::get_stdlib_analysis_path(Some("nightly")).is_ok() {
+        assert!(!blanket_impls
+            .iter()
+            .any(|label| label.contains("SizedTypeProperties")));
+    }
+
+    // Use a copy of the browser so the option doesn't affect other tests.
+    let browser = RustdocBrowser::new(
+        Analysis::load(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/testcrate"))
+            .expect("Failed to load analysis"),
+    );
+    let browser = &browser;
+    browser.set_view_option(crate_id, "#[doc(hidden)] items", false);
+    let x_s_items = browser.list_items(&x_s.0);
+    let blanket = x_s_items.by_prefix("blanket impls (");
+    let shown_impls = browser.list_items(&blanket.0).labels();
+    assert_eq!(shown_impls.len(), blanket_impls.len() - 1);
+    assert!(!shown_impls.contains(&"impl Hidden for T".to_owned()));
+
+    browser.set_view_option(crate_id, "blanket and auto trait impls inline", true);
+
+    let x_s_items = browser.list_items(&x_s.0);
+    assert!(x_s_items.contains_label("impl !core::marker::Send"));
+    assert!(x_s_items.contains_label("impl core::convert::Into<U>"));
+    assert!(!iter_labels(&x_s_items).any(|label| label.starts_with("blanket impls")));
+    assert!(!iter_labels(&x_s_items).any(|label| label.starts_with("auto traits:")));
+}
+
+#[test]
+fn deref_methods() {
+    let crates = BROWSER.list_crates();
+    let crate_id = crates.by_label("testcrate");
+    let root_items = BROWSER.list_items(crate_id);
+    let mod_deref = root_items.by_label("pub mod deref");
+    let mod_deref_items = BROWSER.list_items(&mod_deref.0);
+    let outer = mod_deref_items.by_label("pub struct Outer");
+    let outer_items = BROWSER.list_items(&outer.0);
+    assert_eq!(
+        outer_items.labels_without_std_counts(),
+        &[
+            "0: Middle",
+            "impl core::ops::deref::Deref",
+            "auto traits: RefUnwindSafe ✓ Send ✓ Sync ✓ Unpin ✓ UnwindSafe ✓",
+            "blanket impls",
+            "methods via Deref → Middle",
+            "methods",
+        ]
+    );
+
+    let via_deref = outer_items.by_label("methods via Deref → Middle");
+    assert_eq!(
+        BROWSER.list_items(&via_deref.0).labels(),
+        &[
+            "pub fn middle_method (deref #1 → Middle)",
+            "pub fn inner_method (deref #2 → Inner)",
+        ]
+    );
+
+    // Methods of primitive types are listed if the standard library is loaded.
+    let name = mod_deref_items.by_label("pub struct Name");
+    let name_items = BROWSER.list_items(&name.0);
+    if analysis::get_stdlib_analysis_path(Some("nightly")).is_ok() {
+        let via_deref = BROWSER.list_items(&name_items.by_label("methods via Deref → str").0);
+        assert!(via_deref.contains_label("pub fn len (deref #1 → str)"));
+    } else {
+        assert!(!iter_labels(&name_items).any(|label| label.starts_with("methods via Deref")));
+    }
+}
+
+#[test]
+fn all_methods() {
+    let crates = BROWSER.list_crates();
+    let crate_id = crates.by_label("testcrate");
+    let root_items = BROWSER.list_items(crate_id);
+    let mod_y = root_items.by_label("pub mod y");
+    let mod_y_items = BROWSER.list_items(&mod_y.0);
+    let y_s = mod_y_items.by_label("pub struct S");
+    let y_s_items = BROWSER.list_items(&y_s.0);
+
+    let methods = y_s_items.by_prefix("methods (");
+    let method_labels = BROWSER.list_items(&methods.0).labels();
+    assert!(y_s_items.contains_label(&format!("methods ({})", method_labels.len())));
+    assert!(method_labels.contains(&"fn method (&self) [impl Trait<u64>]".to_owned()));
+    assert!(method_labels.contains(&"pub fn spoopadoop (&self) [impl Self]".to_owned()));
+    // Associated functions which don't take `self`, like `From::from`, aren't methods.
+    assert!(!method_labels.iter().any(|label| label.contains("from")));
+
+    // Each is the method itself, which can be browsed further.
+    let spoopadoop = BROWSER.list_items(&methods.0);
+    let spoopadoop = spoopadoop.by_label("pub fn spoopadoop (&self) [impl Self]");
+    assert_eq!(BROWSER.list_items(&spoopadoop.0).labels(), &["self: &Self"]);
+
+    // A trait's method is shadowed by an inherent one with the same name and receiver.
+    let mod_traits = BROWSER.list_items(&root_items.by_label("pub mod traits").0);
+    let described = BROWSER.list_items(&mod_traits.by_label("pub struct Described").0);
+    let methods = BROWSER.list_items(&described.by_prefix("methods (").0);
+    let describe = iter_labels(&methods)
+        .filter(|label| label.contains(" describe "))
+        .collect::<Vec<_>>();
+    assert_eq!(describe, &["pub fn describe (&self) [impl Self]"]);
+}
+
+#[test]
+fn crate_dependencies() {
+    let crates = BROWSER.list_crates();
+    let testcrate = crates.by_label("testcrate");
+    let root_items = BROWSER.list_items(testcrate);
+    let deps = BROWSER.list_items(&root_items.by_label(dependencies_label()).0);
+    assert!(deps.contains_label("externcrate"), "{:?}", deps.labels());
+    assert!(!deps.contains_label("anyhow"));
+
+    // Dependency rows lead to the crate's contents.
+    let externcrate = deps.by_label("externcrate");
+    let externcrate_items = BROWSER.list_items(&externcrate.0);
+    assert!(externcrate_items.contains_label("pub trait ExternTrait"));
+    assert_eq!(
+        BROWSER
+            .list_items(&externcrate_items.by_label("dependents (1)").0)
+            .labels(),
+        &["testcrate"]
+    );
+}
+
+#[test]
+fn trait_hierarchy() {
+    let crates = BROWSER.list_crates();
+    let testcrate = crates.by_label("testcrate");
+    let root_items = BROWSER.list_items(testcrate);
+    let mod_traits = BROWSER.list_items(&root_items.by_label("pub mod traits").0);
+
+    let base = BROWSER.list_items(&mod_traits.by_label("pub trait Base").0);
+    assert_eq!(base.labels(), &["subtraits (2)"]);
+    let subtraits = BROWSER.list_items(&base.by_label("subtraits (2)").0);
+    assert_eq!(
+        subtraits.labels(),
+        &[
+            "trait testcrate::traits::Sub",
+            "trait testcrate::traits::WhereSub"
+        ]
+    );
+    assert!(items_eq(
+        &subtraits.by_label("trait testcrate::traits::Sub").1,
+        &mod_traits.by_label("pub trait Sub").1,
+    ));
+
+    let sub = BROWSER.list_items(&mod_traits.by_label("pub trait Sub").0);
+    assert_eq!(sub.labels(), &["supertraits (2)"]);
+    let supertraits = BROWSER.list_items(&sub.by_label("supertraits (2)").0);
+    assert_eq!(supertraits.labels(), &["trait Base", "trait Clone"]);
+    assert!(items_eq(
+        &supertraits.by_label("trait Base").1,
+        &mod_traits.by_label("pub trait Base").1,
+    ));
+    if analysis::get_stdlib_analysis_path(Some("nightly")).is_err() {
+        // Clone isn't loaded, so its row leads nowhere and shows the subtrait.
+        let clone = supertraits.by_label("trait Clone");
+        assert!(BROWSER.list_items(&clone.0).is_empty());
+        assert!(items_eq(&clone.1, &mod_traits.by_label("pub trait Sub").1));
+    }
+
+    let where_sub = BROWSER.list_items(&mod_traits.by_label("pub trait WhereSub").0);
+    let supertraits = BROWSER.list_items(&where_sub.by_label("supertraits (1)").0);
+    assert_eq!(supertraits.labels(), &["trait Base"]);
+
+    // Subtraits can be in other crates.
+    let externcrate = BROWSER.list_items(crates.by_label("externcrate"));
+    let extern_trait = BROWSER.list_items(&externcrate.by_label("pub trait ExternTrait").0);
+    let subtraits = BROWSER.list_items(&extern_trait.by_label("subtraits (1)").0);
+    assert_eq!(subtraits.labels(), &["trait testcrate::traits::ExternSub"]);
+}
+
+#[test]
+fn macros() {
+    let crates = BROWSER.list_crates();
+
+    let testcrate = BROWSER.list_items(crates.by_label("testcrate"));
+    let macros = BROWSER.list_items(&testcrate.by_label("macros (1)").0);
+    assert_eq!(macros.labels(), &["pub(crate) macro_rules! square"]);
+    let info = BROWSER.get_info(&macros.by_label("pub(crate) macro_rules! square").1);
+    assert!(info.starts_with("Squares a number.\n"), "{info}");
+    assert!(info.contains("macro_rules! square {"), "{info}");
+
+    let macrocrate = BROWSER.list_items(crates.by_label("macrocrate"));
+    let macros = BROWSER.list_items(&macrocrate.by_label("macros (3)").0);
+    assert_eq!(
+        macros.labels(),
+        &[
+            "pub attribute macro #[passthrough]",
+            "pub derive macro Nothing (#[nothing], #[ignored])",
+            "pub proc macro empty!",
+        ]
+    );
+}
+
+#[test]
+fn attributes() {
+    let crates = BROWSER.list_crates();
+    let testcrate = BROWSER.list_items(crates.by_label("testcrate"));
+    let mod_attrs = BROWSER.list_items(&testcrate.by_label("pub mod attrs").0);
+    assert_eq!(
+        mod_attrs.labels(),
+        &["pub fn old ⚠ deprecated", "pub struct Attributed"]
+    );
+
+    let info = BROWSER.get_info(&mod_attrs.by_label("pub struct Attributed").1);
+    assert!(
+        info.starts_with(
+            "Has attributes.\n\
+            #[non_exhaustive]\n\
+            #[doc(alias = \"thing\")]\n\
+            #[must_use]\n\
+            #[repr(C)]\n\
+            derives: Clone, Debug, PartialEq\n\
+            defined in "
+        ),
+        "{info}"
+    );
+
+    let info = BROWSER.get_info(&mod_attrs.by_label("pub fn old ⚠ deprecated").1);
+    assert!(
+        info.starts_with("⚠ deprecated since 1.2.0: use something else\n"),
+        "{info}"
+    );
+}
+
+#[test]
+fn generics() {
+    let crates = BROWSER.list_crates();
+    let testcrate = BROWSER.list_items(crates.by_label("testcrate"));
+    let mod_traits = BROWSER.list_items(&testcrate.by_label("pub mod traits").0);
+    let bounded = BROWSER.list_items(&mod_traits.by_label("pub fn bounded").0);
+    let generics = BROWSER.list_items(&bounded.by_label("generics (4)").0);
+    // The parameter rustdoc adds for the `impl Fn()` argument is left out, and rustdoc moves the
+    // bounds on function parameters into the where clause.
+    assert_eq!(
+        generics.labels(),
+        &[
+            "'a",
+            "T",
+            "const N: usize",
+            "where T: std::fmt::Debug + Base + Clone",
+        ]
+    );
+    assert!(BROWSER.list_items(&generics.by_label("T").0).is_empty());
+
+    let t_bounds = BROWSER.list_items(
+        &generics
+            .by_label("where T: std::fmt::Debug + Base + Clone")
+            .0,
+    );
+    assert_eq!(
+        t_bounds.labels(),
+        &["trait std::fmt::Debug", "trait Base", "trait Clone"]
+    );
+    assert!(items_eq(
+        &t_bounds.by_label("trait Base").1,
+        &mod_traits.by_label("pub trait Base").1,
+    ));
+
+    // Bounds on type parameters of types stay with the parameter.
+    let trait_items = BROWSER.list_items(&testcrate.by_label("pub trait Trait").0);
+    let generics = BROWSER.list_items(&trait_items.by_label("generics (1)").0);
+    assert_eq!(generics.labels(), &["T: Default"]);
+    assert_eq!(
+        BROWSER
+            .list_items(&generics.by_label("T: Default").0)
+            .labels(),
+        &["trait Default"]
+    );
+}
+
+#[test]
+fn unsafe_code() {
+    let crates = BROWSER.list_crates();
+    let testcrate = BROWSER.list_items(crates.by_label("testcrate"));
+    let unsafe_items = BROWSER.list_items(&testcrate.by_label("unsafe (7)").0);
+    assert_eq!(
+        unsafe_items.labels(),
+        &[
+            "extern fn abs (in testcrate::unsafe_code)",
+            "extern static environ (in testcrate::unsafe_code)",
+            "static mut COUNTER (in testcrate::unsafe_code)",
+            "unsafe fn read (in testcrate::unsafe_code)",
+            "unsafe impl Send for Raw (in testcrate::unsafe_code)",
+            "unsafe impl Zeroable for Raw (in testcrate::unsafe_code)",
+            "unsafe trait Zeroable (in testcrate::unsafe_code)",
+        ]
+    );
+
+    let mod_unsafe = BROWSER.list_items(&testcrate.by_label("pub mod unsafe_code").0);
+    let raw = BROWSER.list_items(&mod_unsafe.by_label("pub struct Raw").0);
+    let read = BROWSER.list_items(&raw.by_label("impl Self").0);
+    assert!(items_eq(
+        &unsafe_items
+            .by_label("unsafe fn read (in testcrate::unsafe_code)")
+            .1,
+        &read.by_label("pub fn read").1,
+    ));
+
+    // Only workspace crates get audited.
+    let externcrate = BROWSER.list_items(crates.by_label("externcrate"));
+    assert!(!externcrate
+        .labels()
+        .iter()
+        .any(|label| label.starts_with("unsafe")));
+}
+
+#[test]
+fn doc_coverage() {
+    let crates = BROWSER.list_crates();
+    let testcrate = BROWSER.list_items(crates.by_label("testcrate"));
+    let modules = BROWSER.list_items(&testcrate.by_label("doc coverage (6%)").0);
+    assert!(modules.contains_label("testcrate::x 0/3 (0%)"));
+    let attrs = BROWSER.list_items(&modules.by_label("testcrate::attrs 1/3 (33%)").0);
+    assert_eq!(
+        attrs.labels(),
+        &[
+            "✗ field Attributed::field",
+            "✗ fn old",
+            "✓ struct Attributed",
+        ]
+    );
+
+    let mod_attrs = BROWSER.list_items(&testcrate.by_label("pub mod attrs").0);
+    assert!(items_eq(
+        &attrs.by_label("✓ struct Attributed").1,
+        &mod_attrs.by_label("pub struct Attributed").1,
+    ));
+
+    let externcrate = BROWSER.list_items(crates.by_label("externcrate"));
+    assert!(!externcrate
+        .labels()
+        .iter()
+        .any(|label| label.starts_with("doc coverage")));
+}
+
+#[test]
+fn doc_links() {
+    let crates = BROWSER.list_crates();
+    let testcrate = BROWSER.list_items(crates.by_label("testcrate"));
+    let mod_links = BROWSER.list_items(&testcrate.by_label("pub mod links").0);
+    let linked = mod_links.by_label("pub struct Linked");
+    let links = BROWSER.doc_links(&linked.1);
+    assert_eq!(
+        links
+            .iter()
+            .map(|(text, _)| text.as_str())
+            .collect::<Vec<_>>(),
+        &[
+            "`crate::x::S`",
+            "`Linked::go`",
+            "`externcrate::ExternTrait`",
+            "`Vec`",
+        ]
+    );
+
+    // Following a link gives the items to select in each pane to get to its target.
+    let target = |text: &str| {
+        let (_, target) = links.iter().find(|(t, _)| t == text).unwrap();
+        target.clone()
+    };
+    let path = BROWSER
+        .item_path(&target("`crate::x::S`").unwrap())
+        .unwrap();
+    let mod_x = BROWSER.list_items(&testcrate.by_label("pub mod x").0);
+    assert_eq!(
+        path,
+        &[
+            crates.by_label("testcrate").clone(),
+            testcrate.by_label("pub mod x").0.clone(),
+            mod_x.by_label("pub struct S").0.clone(),
+        ]
+    );
+
+    let path = BROWSER.item_path(&target("`Linked::go`").unwrap()).unwrap();
+    let linked_items = BROWSER.list_items(&linked.0);
+    let impl_items = BROWSER.list_items(&linked_items.by_label("impl Self").0);
+    assert_eq!(
+        &path[1..],
+        &[
+            testcrate.by_label("pub mod links").0.clone(),
+            linked.0.clone(),
+            linked_items.by_label("impl Self").0.clone(),
+            impl_items.by_label("pub fn go").0.clone(),
+        ]
+    );
+
+    // Links can go to other crates.
+    let path = BROWSER
+        .item_path(&target("`externcrate::ExternTrait`").unwrap())
+        .unwrap();
+    let externcrate = BROWSER.list_items(crates.by_label("externcrate"));
+    assert_eq!(
+        path,
+        &[
+            crates.by_label("externcrate").clone(),
+            externcrate.by_label("pub trait ExternTrait").0.clone(),
+        ]
+    );
+
+    // Without the standard library loaded, links to it can't be followed.
+    assert_eq!(
+        target("`Vec`").is_some(),
+        rsbrowse::analysis::get_stdlib_analysis_path(Some("nightly")).is_ok()
+    );
+}
+
+#[test]
+fn search_docs() {
+    let results = BROWSER.search_docs("back to").unwrap();
+    assert_eq!(results.len(), 1);
+    let result = &results[0];
+    assert_eq!(result.label, "fn testcrate::links::Linked::go");
+    assert_eq!(result.detail, "Back to Linked.");
+    assert_eq!(result.highlight, Some(0..7));
+
+    let path = BROWSER.item_path(&result.id).unwrap();
+    let crates = BROWSER.list_crates();
+    let testcrate = BROWSER.list_items(crates.by_label("testcrate"));
+    let mod_links = BROWSER.list_items(&testcrate.by_label("pub mod links").0);
+    assert_eq!(
+        &path[..3],
+        &[
+            crates.by_label("testcrate").clone(),
+            testcrate.by_label("pub mod links").0.clone(),
+            mod_links.by_label("pub struct Linked").0.clone(),
+        ]
+    );
+}
+
+#[test]
+fn search_signatures() {
+    let results = BROWSER
+        .search_signatures("usize, &str -> String")
+        .unwrap()
+        .unwrap();
+    let result = results
+        .iter()
+        .find(|result| result.label == "testcrate::sigs::repeat")
+        .unwrap();
+    assert_eq!(result.detail, "fn repeat(s: &str, times: usize) -> String");
+    assert!(BROWSER.item_path(&result.id).is_some());
+
+    assert!(BROWSER
+        .search_signatures("&str -> Result<")
+        .unwrap()
+        .is_err());
+}
+
+#[test]
+fn constructors() {
+    let crates = BROWSER.list_crates();
+    let testcrate = BROWSER.list_items(crates.by_label("testcrate"));
+    let mod_ctors = BROWSER.list_items(&testcrate.by_label("pub mod ctors").0);
+    let widget = BROWSER.list_items(&mod_ctors.by_label("pub struct Widget").0);
+    let ctors = BROWSER.list_items(&widget.by_label("constructors (9)").0);
+    assert_eq!(
+        ctors.labels(),
+        &[
+            "fn testcrate::ctors::Widget::default() -> Widget [impl core::default::Default]",
+            "fn testcrate::ctors::Widget::from(_: crate::sigs::ParseError) -> Self \
+             [impl core::convert::From<ParseError>]",
+            "fn testcrate::ctors::Widget::from(size: u32) -> Self [impl core::convert::From<u32>]",
+            "fn testcrate::ctors::Widget::from_str(s: &str) -> Result<Self, Self::Err> \
+             [impl core::str::traits::FromStr]",
+            "fn testcrate::ctors::Widget::new() -> Self",
+            "fn testcrate::ctors::Widget::try_from(size: i64) -> Result<Self, Self::Error> \
+             [impl core::convert::TryFrom<i64>]",
+            "fn testcrate::ctors::Widget::try_new(size: u32) -> Result<Widget, String>",
+            "fn testcrate::ctors::boxed() -> Box<Widget>",
+            "fn testcrate::ctors::maybe(size: Option<u32>) -> Option<Widget>",
+        ]
+    );
+
+    // A type nothing returns has no constructors row.
+    let parse_error = BROWSER.list_items(
+        &BROWSER
+            .list_items(&testcrate.by_label("pub mod sigs").0)
+            .by_label("pub struct ParseError")
+            .0,
+    );
+    assert!(!parse_error
+        .labels()
+        .iter()
+        .any(|label| label.starts_with("constructors")));
+}
+
+#[test]
+fn conversions() {
+    let crates = BROWSER.list_crates();
+    let testcrate = BROWSER.list_items(crates.by_label("testcrate"));
+    let mod_ctors = BROWSER.list_items(&testcrate.by_label("pub mod ctors").0);
+    let widget = BROWSER.list_items(&mod_ctors.by_label("pub struct Widget").0);
+    let conversions = BROWSER.list_items(&widget.by_label("conversions (6)").0);
+    assert_eq!(
+        conversions.labels(),
+        &[
+            "from ParseError via From",
+            "from i64 via TryFrom",
+            "from str via FromStr",
+            "from u32 via From",
+            "into &u32 via AsRef",
+            "into u32 via From",
+        ]
+    );
+
+    // Only the standard library's conversion traits count, not others with the same name.
+    assert!(!conversions.contains_label("from String via From"));
+
+    // Conversions lead to the other type, where they can be followed back.
+    let parse_error = BROWSER.list_items(&conversions.by_label("from ParseError via From").0);
+    let back = BROWSER.list_items(&parse_error.by_label("conversions (1)").0);
+    assert_eq!(back.labels(), &["into Widget via From"]);
+}
+
+#[test]
+fn impl_parent() {
+    // An impl mentioning two local types is listed under both; its items belong to the type it's
+    // for.
+    let crates = BROWSER.list_crates();
+    let testcrate = BROWSER.list_items(crates.by_label("testcrate"));
+    let mod_ctors = BROWSER.list_items(&testcrate.by_label("pub mod ctors").0);
+    let widget = BROWSER.list_items(&mod_ctors.by_label("pub struct Widget").0);
+    let ctors = BROWSER.list_items(&widget.by_prefix("constructors (").0);
+    let (from, _) = ctors
+        .iter()
+        .find(|(label, _)| label.contains("from(_: crate::sigs::ParseError)"))
+        .unwrap()
+        .1
+        .clone();
+    let path = BROWSER.item_path(&from).unwrap();
+    assert_eq!(
+        &path[..3],
+        &[
+            crates.by_label("testcrate").clone(),
+            testcrate.by_label("pub mod ctors").0.clone(),
+            mod_ctors.by_label("pub struct Widget").0.clone(),
+        ]
+    );
+}
+
+#[test]
+fn query_impl() {
+    let answer = BROWSER.query_impl("x::S", "Describe").unwrap().unwrap();
+    assert_eq!(
+        answer.summary,
+        "testcrate::x::S implements testcrate::traits::Describe by a blanket impl, if it meets \
+         the impl's bounds"
+    );
+    let impls = answer
+        .impls
+        .iter()
+        .map(|r| (r.label.as_str(), r.detail.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        impls,
+        &[("blanket impl", "impl<T> Describe for T where T: Display")]
+    );
+
+    let answer = BROWSER.query_impl("x::S", "Send").unwrap().unwrap();
+    assert_eq!(
+        answer.summary,
+        "testcrate::x::S does not implement core::marker::Send"
+    );
+    assert_eq!(answer.impls[0].detail, "impl !core::marker::Send for S");
+
+    // Each impl can be gone to.
+    let path = BROWSER.item_path(&answer.impls[0].id).unwrap();
+    assert_eq!(path.len(), 4);
+
+    let err = BROWSER
+        .query_impl("x::S", "Nonexistent")
+        .unwrap()
+        .unwrap_err();
+    assert_eq!(err.to_string(), "no trait named \"Nonexistent\" was found");
+}
+
+#[test]
+fn generic_substitution() {
+    let crates = BROWSER.list_crates();
+    let testcrate = BROWSER.list_items(crates.by_label("testcrate"));
+    let mod_pairs = BROWSER.list_items(&testcrate.by_label("pub mod pairs").0);
+
+    // Browsing the type itself shows its parameters.
+    let pair = BROWSER.list_items(&mod_pairs.by_label("pub struct Pair").0);
+    assert!(pair.contains_label("pub first: A"), "{:?}", pair.labels());
+
+    // Browsing it through a field shows the field's arguments in their place.
+    let holder = BROWSER.list_items(&mod_pairs.by_label("pub struct Holder").0);
+    let pair = BROWSER.list_items(&holder.by_label("pub pair: Pair<u32, String>").0);
+    assert!(pair.contains_label("pub first: u32"), "{:?}", pair.labels());
+    assert!(pair.contains_label("pub second: String"));
+
+    let methods = BROWSER.list_items(&pair.by_label("impl Self").0);
+    assert_eq!(
+        methods.labels(),
+        &["pub fn first", "pub fn swap", "generics (2)"]
+    );
+    let swap = BROWSER.list_items(&methods.by_label("pub fn swap").0);
+    assert_eq!(swap.labels(), &["self: Self", "-> Pair<String, u32>"]);
+    let first = BROWSER.list_items(&methods.by_label("pub fn first").0);
+    assert_eq!(first.labels(), &["self: &Self", "-> &u32"]);
+
+    // So do the groups of its methods, impls and generic parameters.
+    let all_methods = BROWSER.list_items(&pair.by_prefix("methods (").0);
+    assert!(
+        all_methods.contains_label("pub fn swap (self) [impl Self]"),
+        "{:?}",
+        all_methods.labels()
+    );
+    let swap = BROWSER.list_items(&all_methods.by_label("pub fn swap (self) [impl Self]").0);
+    assert_eq!(swap.labels(), &["self: Self", "-> Pair<String, u32>"]);
+    let auto_traits = BROWSER.list_items(&pair.by_prefix("auto traits").0);
+    assert!(
+        auto_traits.contains_label("impl core::marker::Send where u32: Send, String: Send"),
+        "{:?}",
+        auto_traits.labels()
+    );
+    let generics = BROWSER.list_items(&pair.by_label("generics (2)").0);
+    assert_eq!(generics.labels(), &["A (= u32)", "B (= String)"]);
+
+    // Arguments which are themselves generic are carried through.
+    let nested = BROWSER.list_items(&holder.by_label("pub nested: Pair<Pair<u8, bool>, char>").0);
+    let inner = BROWSER.list_items(&nested.by_label("pub first: Pair<u8, bool>").0);
+    assert!(
+        inner.contains_label("pub first: u8"),
+        "{:?}",
+        inner.labels()
+    );
+    assert!(inner.contains_label("pub second: bool"));
+}
+
+#[test]
+fn layouts() {
+    // Make sure the analysis has been generated.
+    let crates = BROWSER.list_crates();
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/testcrate");
+    TypeLayouts::generate(path, Some("nightly")).expect("Failed to generate layouts");
+    let browser = RustdocBrowser::new(Analysis::load(path).expect("Failed to load analysis"))
+        .with_layouts(TypeLayouts::load(path).expect("Failed to load layouts"));
+    let browser = &browser;
+
+    let testcrate = browser.list_items(crates.by_label("testcrate"));
+    let mod_ctors = browser.list_items(&testcrate.by_label("pub mod ctors").0);
+    let widget = mod_ctors.by_label("pub struct Widget");
+    assert!(browser
+        .get_info(&widget.1)
+        .ends_with("\n\nlayout: 4 bytes, alignment 4 bytes\n  offset 0: size (4 bytes)\n"));
+
+    // Generic types have no one layout.
+    let mod_pairs = browser.list_items(&testcrate.by_label("pub mod pairs").0);
+    let pair = mod_pairs.by_label("pub struct Pair");
+    assert!(!browser.get_info(&pair.1).contains("layout:"));
+}
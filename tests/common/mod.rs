@@ -0,0 +1,18 @@
+//! Analysis data shared by the tests.
+
+// Not every test uses everything here.
+#![allow(dead_code)]
+
+use rsbrowse::analysis::Analysis;
+use std::path::PathBuf;
+
+lazy_static! {
+    pub static ref ANALYSIS: Analysis = generate("testcrate");
+}
+
+/// Generate and load the analysis of one of the crates in the tests directory.
+pub fn generate(name: &str) -> Analysis {
+    let path = PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/tests")).join(name);
+    Analysis::generate(&path, Some("nightly")).expect("Failed to generate analysis data.");
+    Analysis::load(&path).expect("Failed to load analysis")
+}
@@ -0,0 +1,161 @@
+#[macro_use]
+extern crate lazy_static;
+
+mod common;
+
+use common::ANALYSIS;
+use rsbrowse::coverage::{self, Coverage};
+
+fn module<'a>(
+    crate_: &'a coverage::CrateCoverage<'a>,
+    path: &str,
+) -> &'a coverage::ModuleCoverage<'a> {
+    crate_
+        .modules
+        .iter()
+        .find(|module| module.path == path)
+        .expect("module not found")
+}
+
+fn items(module: &coverage::ModuleCoverage) -> Vec<(String, bool)> {
+    module
+        .items
+        .iter()
+        .map(|item| (item.label.clone(), item.documented))
+        .collect()
+}
+
+#[test]
+fn crate_coverage() {
+    let crate_ = coverage::crate_coverage(&ANALYSIS, "testcrate");
+
+    // Private items (like the enum here) and items of trait impls don't count.
+    let x = module(&crate_, "testcrate::x");
+    assert_eq!(
+        items(x),
+        vec![
+            ("field S::int_field".to_owned(), false),
+            ("fn S::f".to_owned(), false),
+            ("struct S".to_owned(), false),
+        ]
+    );
+
+    let unsafe_code = module(&crate_, "testcrate::unsafe_code");
+    assert!(items(unsafe_code).contains(&("fn Raw::read".to_owned(), true)));
+    // The pointer in the tuple struct Raw is private anyway, but public tuple fields aren't
+    // expected to be documented either.
+    assert!(!items(unsafe_code)
+        .iter()
+        .any(|(label, _)| label.starts_with("field")));
+
+    // The crate root counts as part of itself, and trait methods are public if the trait is.
+    let root = module(&crate_, "testcrate");
+    assert!(items(root).contains(&("mod testcrate".to_owned(), false)));
+    assert!(items(root).contains(&("fn Trait::method".to_owned(), false)));
+    assert_eq!(root.documented(), 0);
+
+    // Only items reachable from the crate root count, even if they're declared `pub`, and the
+    // module a glob re-exports from isn't reachable itself.
+    let private = module(&crate_, "testcrate::api::private");
+    assert_eq!(
+        items(private),
+        vec![
+            ("fn Reexported::new".to_owned(), false),
+            ("struct Reexported".to_owned(), false),
+        ]
+    );
+    let inner = module(&crate_, "testcrate::api::private::inner");
+    assert_eq!(items(inner), vec![("fn glob_fn".to_owned(), false)]);
+
+    // Neither do hidden items.
+    let traits = module(&crate_, "testcrate::traits");
+    assert!(!items(traits).contains(&("trait Hidden".to_owned(), false)));
+    assert!(items(traits).contains(&("trait Describe".to_owned(), false)));
+
+    let attrs = module(&crate_, "testcrate::attrs");
+    assert_eq!((attrs.documented(), attrs.total()), (1, 3));
+    assert_eq!(attrs.percent().floor(), 33.);
+    assert_eq!(
+        crate_.total(),
+        crate_.modules.iter().map(|m| m.items.len()).sum::<usize>()
+    );
+}
+
+#[test]
+fn index() {
+    let index = coverage::CoverageIndex::build(&ANALYSIS);
+    let crate_ = coverage::crate_coverage(&ANALYSIS, "testcrate");
+    let indexed = index.crate_coverage(&ANALYSIS, "testcrate");
+    assert_eq!(index.percent("testcrate"), crate_.percent());
+    let paths = |crate_: &coverage::CrateCoverage| {
+        crate_
+            .modules
+            .iter()
+            .map(|module| (module.path.clone(), items(module)))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(paths(&indexed), paths(&crate_));
+
+    let x = module(&crate_, "testcrate::x");
+    let indexed_x = index.module_coverage(&ANALYSIS, &x.id).unwrap();
+    assert_eq!(items(&indexed_x), items(x));
+}
+
+#[test]
+fn report() {
+    let crates = coverage::workspace_coverage(&ANALYSIS);
+    assert_eq!(
+        crates.iter().map(|c| c.crate_name).collect::<Vec<_>>(),
+        &["testcrate"]
+    );
+
+    let mut out = vec![];
+    coverage::write_text(&crates, &mut out).unwrap();
+    let report = String::from_utf8(out).unwrap();
+    assert!(
+        report.contains(
+            "\n  testcrate::attrs: 1/3 (33.3%)\n    \
+            undocumented field Attributed::field\n    \
+            undocumented fn old\n"
+        ),
+        "{report}"
+    );
+    let total = format!(
+        "total: {}/{} documented ({:.1}%)\n",
+        crates.documented(),
+        crates.total(),
+        crates.percent()
+    );
+    assert!(report.ends_with(&total), "{report}");
+
+    let json = coverage::to_json(&crates);
+    assert_eq!(json["total"], crates.total());
+    let attrs = json["crates"][0]["modules"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|module| module["path"] == "testcrate::attrs")
+        .unwrap();
+    assert_eq!(attrs["documented"], 1);
+    assert_eq!(
+        attrs["undocumented"],
+        serde_json::json!(["field Attributed::field", "fn old"])
+    );
+}
+
+#[test]
+fn command_json() {
+    // Generate the analysis here first, so the command doesn't run alongside another test doing
+    // the same.
+    lazy_static::initialize(&ANALYSIS);
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_rsbrowse"))
+        .args(["coverage", "--json", "--toolchain=nightly"])
+        .arg(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/testcrate"))
+        .output()
+        .expect("Failed to run rsbrowse");
+    assert!(output.status.success(), "{output:?}");
+
+    // Nothing but the report goes to stdout, so it can be redirected to a file.
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["crates"][0]["name"], "testcrate");
+}
@@ -0,0 +1,159 @@
+#[macro_use]
+extern crate lazy_static;
+
+mod common;
+
+use common::generate;
+use rsbrowse::analysis::Analysis;
+use rsbrowse::diff::{self, ChangeKind};
+use std::path::{Path, PathBuf};
+
+lazy_static! {
+    static ref OLD: Analysis = generate("semver_old");
+    static ref NEW: Analysis = generate("semver_new");
+}
+
+#[test]
+fn diff_changes() {
+    let changes = diff::diff(&OLD, &NEW);
+    let summary = changes
+        .iter()
+        .map(|c| (c.kind, c.path.join("::")))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        &[
+            (ChangeKind::Changed, "semvercrate::Convert".to_owned()),
+            (ChangeKind::Added, "semvercrate::Event::KeyPress".to_owned()),
+            (ChangeKind::Added, "semvercrate::Point::z".to_owned()),
+            (
+                ChangeKind::Added,
+                "semvercrate::Render::describe".to_owned()
+            ),
+            (ChangeKind::Added, "semvercrate::Render::name".to_owned()),
+            (ChangeKind::Added, "semvercrate::Shape::Triangle".to_owned()),
+            (ChangeKind::Changed, "semvercrate::Visit".to_owned()),
+            (ChangeKind::Added, "semvercrate::added".to_owned()),
+            (ChangeKind::Changed, "semvercrate::parse".to_owned()),
+            (ChangeKind::Removed, "semvercrate::removed".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn diff_text() {
+    let changes = diff::diff(&OLD, &NEW);
+    let mut out = vec![];
+    diff::write_text(&changes, &mut out).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "\
+~ semvercrate::Convert
+    - pub trait Convert<T>
+    + pub trait Convert<T, U = ()>
++ semvercrate::Event::KeyPress
+    + KeyPress
++ semvercrate::Point::z
+    + pub z: i32
++ semvercrate::Render::describe
+    + fn describe(self: &Self) -> String
++ semvercrate::Render::name
+    + fn name(self: &Self) -> String;
++ semvercrate::Shape::Triangle
+    + Triangle
+~ semvercrate::Visit
+    - pub trait Visit<T>
+    + pub trait Visit<T, U>
++ semvercrate::added
+    + pub fn added()
+~ semvercrate::parse
+    - pub fn parse(input: &str) -> Option<u32>
+    + pub fn parse(input: &str, radix: u32) -> Option<u32>
+- semvercrate::removed
+    - pub fn removed()
+"
+    );
+}
+
+#[test]
+fn no_changes() {
+    let path = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/semver_old"));
+    let same = Analysis::load(path).expect("Failed to load analysis");
+    assert!(diff::diff(&OLD, &same).is_empty());
+}
+
+#[test]
+fn diff_browser() {
+    use rsbrowse::browser_diff::DiffBrowser;
+    use rsbrowse::browser_trait::Browser;
+
+    let browser = DiffBrowser::new(diff::diff(&OLD, &NEW), PathBuf::new(), PathBuf::new());
+    let browser = &browser;
+    let crates = browser.list_crates();
+    assert_eq!(
+        crates
+            .iter()
+            .map(|(label, _)| label.as_str())
+            .collect::<Vec<_>>(),
+        &["semvercrate"]
+    );
+
+    let items = browser.list_items(&crates[0].1);
+    assert_eq!(
+        items
+            .iter()
+            .map(|(label, _)| label.as_str())
+            .collect::<Vec<_>>(),
+        &[
+            "~ trait Convert",
+            "Event (1)",
+            "Point (1)",
+            "Render (2)",
+            "Shape (1)",
+            "~ trait Visit",
+            "+ fn added",
+            "~ fn parse",
+            "- fn removed"
+        ]
+    );
+
+    let point_items = browser.list_items(&items[2].1 .0);
+    assert_eq!(
+        point_items
+            .iter()
+            .map(|(label, _)| label.as_str())
+            .collect::<Vec<_>>(),
+        &["+ field z"]
+    );
+}
+
+#[test]
+fn semver_classification() {
+    use rsbrowse::semver::{self, Severity};
+
+    let changes = diff::diff(&OLD, &NEW);
+    let mut out = vec![];
+    let severity = semver::write_summary(&changes, &NEW, &mut out).unwrap();
+    assert_eq!(severity, Severity::Major);
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "\
+minor ~ semvercrate::Convert: generic parameter with a default added
+minor + semvercrate::Event::KeyPress: variant added to #[non_exhaustive] enum
+major + semvercrate::Point::z: public field added to struct with all public fields
+minor + semvercrate::Render::describe: provided trait method added
+major + semvercrate::Render::name: required trait method added
+major + semvercrate::Shape::Triangle: variant added to exhaustive enum
+major ~ semvercrate::Visit: generic parameter without a default added
+minor + semvercrate::added: public item added
+major ~ semvercrate::parse: function signature changed
+major - semvercrate::removed: public item removed
+required version bump: major (6 major, 4 minor, 0 patch changes)
+"
+    );
+
+    let same = diff::diff(&OLD, &OLD);
+    let mut out = vec![];
+    let severity = semver::write_summary(&same, &OLD, &mut out).unwrap();
+    assert_eq!(severity, Severity::Patch);
+}
@@ -0,0 +1,133 @@
+#[macro_use]
+extern crate lazy_static;
+
+mod common;
+
+use common::ANALYSIS;
+use rsbrowse::graph::{self, Format, Graph};
+
+fn graph_of(path: &str, depth: usize) -> Graph {
+    let path = path.split("::").collect::<Vec<_>>();
+    let id = ANALYSIS.find_path(&path).expect("item not found");
+    graph::type_graph(&ANALYSIS, id, depth)
+}
+
+fn edge_labels(graph: &Graph) -> Vec<String> {
+    graph
+        .edges
+        .iter()
+        .map(|edge| {
+            let (from, to, label) = graph.edge_names(edge);
+            format!("{from} -{label}-> {to}")
+        })
+        .collect()
+}
+
+#[test]
+fn type_graph() {
+    let graph = graph_of("testcrate::x::S", 1);
+    let edges = edge_labels(&graph);
+    for edge in [
+        "testcrate::x::S -field string_field-> alloc::string::String",
+        "testcrate::x::S -field opt_field-> core::option::Option",
+        "testcrate::x::S -field opt_field-> core::result::Result",
+        "testcrate::x::S -field opt_field-> std::io::error::Error",
+        "testcrate::x::S -field fn_field-> alloc::boxed::Box",
+        "testcrate::x::S -field fn_field-> core::ops::function::Fn",
+        "testcrate::x::S -implements-> core::fmt::Display",
+        "testcrate::x::S -implements-> externcrate::ExternTrait",
+    ] {
+        assert!(edges.contains(&edge.to_owned()), "{edge} not in {edges:#?}");
+    }
+    // Auto trait and blanket impls are left out.
+    assert!(!edges.iter().any(|e| e.contains("core::marker::Send")));
+
+    // Depth 0 is just the item itself.
+    let graph = graph_of("testcrate::x::E", 0);
+    assert_eq!(graph.nodes.len(), 1);
+    assert!(graph.edges.is_empty());
+
+    let graph = graph_of("testcrate::deref::Handle", 2);
+    assert_eq!(
+        edge_labels(&graph),
+        &[
+            "testcrate::deref::Handle -alias of-> testcrate::deref::Outer",
+            "testcrate::deref::Outer -field 0-> testcrate::deref::Middle",
+            "testcrate::deref::Outer -implements-> core::ops::deref::Deref",
+        ]
+    );
+}
+
+#[test]
+fn variant_edges() {
+    let graph = graph_of("testcrate::x::E", 1);
+    assert_eq!(
+        edge_labels(&graph),
+        &[
+            "testcrate::x::E -variant TupleVariant-> testcrate::x::S",
+            "testcrate::x::E -variant StructVariant-> testcrate::x::S",
+        ]
+    );
+}
+
+#[test]
+fn export_formats() {
+    let graph = graph_of("testcrate::deref::Handle", 1);
+    let mut dot = vec![];
+    graph.write(Format::Dot, &mut dot).unwrap();
+    assert_eq!(
+        String::from_utf8(dot).unwrap(),
+        r#"digraph {
+    rankdir=LR;
+    node [shape=box];
+    n0 [label="type testcrate::deref::Handle"];
+    n1 [label="struct testcrate::deref::Outer"];
+    n0 -> n1 [label="alias of"];
+}
+"#
+    );
+
+    let mut mermaid = vec![];
+    graph.write(Format::Mermaid, &mut mermaid).unwrap();
+    assert_eq!(
+        String::from_utf8(mermaid).unwrap(),
+        r#"graph LR
+    n0["type testcrate::deref::Handle"]
+    n1["struct testcrate::deref::Outer"]
+    n0 -->|"alias of"| n1
+"#
+    );
+}
+
+#[test]
+fn crate_graph() {
+    let graph = graph::crate_graph(&ANALYSIS);
+    let edges = edge_labels(&graph);
+    assert!(
+        edges.contains(&"testcrate --> externcrate".to_owned()),
+        "{edges:#?}"
+    );
+    assert!(!edges
+        .iter()
+        .any(|e| e.starts_with("externcrate -") && e.ends_with("testcrate")));
+    // Crates that aren't referenced by anything are still shown.
+    assert!(graph.nodes.iter().any(|node| node.name == "anyhow"));
+}
+
+#[test]
+fn command_output() {
+    // Generate the analysis here first, so the command doesn't run alongside another test doing
+    // the same.
+    lazy_static::initialize(&ANALYSIS);
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_rsbrowse"))
+        .args(["graph", "--toolchain=nightly"])
+        .arg(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/testcrate"))
+        .output()
+        .expect("Failed to run rsbrowse");
+    assert!(output.status.success(), "{output:?}");
+
+    // Nothing but the graph goes to stdout, so it can be redirected to a file.
+    let dot = String::from_utf8(output.stdout).unwrap();
+    assert!(dot.starts_with("digraph {\n"), "{dot}");
+    assert!(dot.ends_with("}\n"), "{dot}");
+}
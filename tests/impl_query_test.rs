@@ -0,0 +1,90 @@
+#[macro_use]
+extern crate lazy_static;
+
+mod common;
+
+use common::ANALYSIS;
+use rsbrowse::impl_query::{self, Answer, ImplKind};
+
+/// The answer for a type and trait, and the kinds of impls found.
+fn query(type_path: &str, trait_path: &str) -> (Answer, Vec<ImplKind>) {
+    let type_id = impl_query::resolve(&ANALYSIS, type_path, false).unwrap();
+    let trait_id = impl_query::resolve(&ANALYSIS, trait_path, true).unwrap();
+    let impls = impl_query::find_impls(&ANALYSIS, &type_id, &trait_id);
+    (
+        Answer::from_impls(&impls),
+        impls.iter().map(|m| m.kind).collect(),
+    )
+}
+
+#[test]
+fn direct() {
+    assert_eq!(
+        query("x::S", "Display"),
+        (Answer::Yes, vec![ImplKind::Direct])
+    );
+    // The impl is in testcrate, and the trait in externcrate.
+    assert_eq!(
+        query("testcrate::x::S", "externcrate::ExternTrait"),
+        (Answer::Yes, vec![ImplKind::Direct])
+    );
+    assert_eq!(
+        query("Widget", "FromStr"),
+        (Answer::Yes, vec![ImplKind::Direct])
+    );
+}
+
+#[test]
+fn blanket() {
+    let (answer, kinds) = query("x::S", "Describe");
+    assert_eq!(answer, Answer::IfBoundsHold);
+    assert_eq!(kinds, vec![ImplKind::Blanket]);
+
+    // String's crate doesn't know about testcrate's blanket impl, so it's found there.
+    assert_eq!(
+        query("String", "Describe"),
+        (Answer::IfBoundsHold, vec![ImplKind::Blanket])
+    );
+}
+
+#[test]
+fn primitives() {
+    assert_eq!(
+        query("u8", "traits::Base"),
+        (Answer::Yes, vec![ImplKind::Direct])
+    );
+    assert_eq!(
+        query("&str", "Describe"),
+        (Answer::IfBoundsHold, vec![ImplKind::Blanket])
+    );
+    assert_eq!(query("str", "traits::Base"), (Answer::NotFound, vec![]));
+}
+
+#[test]
+fn auto_traits() {
+    assert_eq!(query("y::S", "Sync"), (Answer::Yes, vec![ImplKind::Auto]));
+    // x::S has a field that isn't Send, so the compiler's impl is a negative one.
+    assert_eq!(query("x::S", "Send"), (Answer::No, vec![ImplKind::Auto]));
+    // A written impl takes the place of the compiler's.
+    assert_eq!(query("Raw", "Send"), (Answer::Yes, vec![ImplKind::Direct]));
+}
+
+#[test]
+fn no_impl() {
+    assert_eq!(query("Widget", "traits::Base"), (Answer::NotFound, vec![]));
+    assert_eq!(
+        Answer::NotFound.describe("testcrate::ctors::Widget", "testcrate::traits::Base"),
+        "no impl of testcrate::traits::Base for testcrate::ctors::Widget was found"
+    );
+}
+
+#[test]
+fn resolve_errors() {
+    let err = impl_query::resolve(&ANALYSIS, "S", false).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "\"S\" could be any of: testcrate::x::S, testcrate::y::S, testcrate::z::S"
+    );
+    let err = impl_query::resolve(&ANALYSIS, "Widget", true).unwrap_err();
+    assert_eq!(err.to_string(), "no trait named \"Widget\" was found");
+}
@@ -0,0 +1,17 @@
+use proc_macro::TokenStream;
+
+/// Derives nothing at all.
+#[proc_macro_derive(Nothing, attributes(nothing, ignored))]
+pub fn derive_nothing(_input: TokenStream) -> TokenStream {
+    TokenStream::new()
+}
+
+#[proc_macro_attribute]
+pub fn passthrough(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    item
+}
+
+#[proc_macro]
+pub fn empty(_input: TokenStream) -> TokenStream {
+    TokenStream::new()
+}
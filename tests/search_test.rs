@@ -0,0 +1,50 @@
+#[macro_use]
+extern crate lazy_static;
+
+mod common;
+
+use common::ANALYSIS;
+use rsbrowse::search::DocIndex;
+
+lazy_static! {
+    static ref INDEX: DocIndex = DocIndex::build(&ANALYSIS);
+}
+
+#[test]
+fn phrase() {
+    let matches = INDEX.search(&ANALYSIS, "squares A NUMBER");
+    assert_eq!(matches.len(), 1);
+    let m = &matches[0];
+    assert_eq!(m.kind, "macro");
+    assert_eq!(m.path, "testcrate::macros::square");
+    assert!(m.phrase);
+    assert_eq!(&m.snippet[m.highlight.clone()], "Squares a number");
+}
+
+#[test]
+fn words() {
+    // Not a phrase, but all the words are there. Line breaks don't matter.
+    let matches = INDEX.search(&ANALYSIS, "valid pointer");
+    assert_eq!(matches.len(), 1);
+    let m = &matches[0];
+    assert_eq!(m.path, "testcrate::unsafe_code::Raw::read");
+    assert_eq!(m.kind, "fn");
+    assert!(!m.phrase);
+    assert_eq!(m.snippet, "# Safety The pointer must be valid.");
+    assert_eq!(&m.snippet[m.highlight.clone()], "valid");
+
+    assert!(INDEX.search(&ANALYSIS, "valid nonexistentword").is_empty());
+    assert!(INDEX.search(&ANALYSIS, "").is_empty());
+}
+
+#[test]
+fn markdown() {
+    // Code marks and links are ignored when matching phrases.
+    let matches = INDEX.search(&ANALYSIS, "holds a vec");
+    assert_eq!(matches.len(), 1);
+    let m = &matches[0];
+    assert_eq!(m.path, "testcrate::links::Linked");
+    assert!(m.phrase);
+    assert!(m.snippet.starts_with('…'), "{}", m.snippet);
+    assert_eq!(&m.snippet[m.highlight.clone()], "holds a Vec");
+}
@@ -0,0 +1,43 @@
+pub struct Unchanged;
+
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+pub enum Shape {
+    Circle,
+    Square,
+    Triangle,
+}
+
+pub trait Render {
+    fn render(&self) -> String;
+    fn name(&self) -> String;
+    fn describe(&self) -> String {
+        self.render()
+    }
+}
+
+#[non_exhaustive]
+pub enum Event {
+    Click,
+    KeyPress,
+}
+
+pub trait Convert<T, U = ()> {}
+
+pub trait Visit<T, U> {}
+
+pub fn parse(input: &str, radix: u32) -> Option<u32> {
+    u32::from_str_radix(input, radix).ok()
+}
+
+pub fn added() {}
+
+mod private {
+    pub fn reexported() {}
+}
+
+pub use private::reexported;
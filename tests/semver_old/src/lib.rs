@@ -0,0 +1,36 @@
+pub struct Unchanged;
+
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+pub enum Shape {
+    Circle,
+    Square,
+}
+
+pub trait Render {
+    fn render(&self) -> String;
+}
+
+#[non_exhaustive]
+pub enum Event {
+    Click,
+}
+
+pub trait Convert<T> {}
+
+pub trait Visit<T> {}
+
+pub fn parse(input: &str) -> Option<u32> {
+    input.parse().ok()
+}
+
+pub fn removed() {}
+
+mod private {
+    pub fn reexported() {}
+}
+
+pub use private::reexported;
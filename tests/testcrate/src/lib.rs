@@ -1,3 +1,28 @@
+pub mod api {
+    mod private {
+        pub struct Reexported;
+
+        /// Public, but not reachable from outside the crate.
+        pub struct Unexported;
+
+        impl Reexported {
+            pub fn new() -> Self {
+                Reexported
+            }
+        }
+
+        pub mod inner {
+            pub fn glob_fn() {}
+
+            fn not_imported_by_glob() {}
+        }
+    }
+
+    pub use self::private::inner::*;
+    pub use self::private::Reexported as Renamed;
+    pub use externcrate::ExternTrait;
+}
+
 pub mod x {
     pub struct S {
         pub int_field: i32,
@@ -7,7 +32,9 @@ pub mod x {
     }
 
     impl S {
-        pub fn f(&self, e_arg: E) -> S {}
+        pub fn f(&self, e_arg: E) -> S {
+            unimplemented!()
+        }
     }
 
     impl std::fmt::Display for S {
@@ -56,3 +83,293 @@ pub trait Trait<T: Default> {
         Default::default()
     }
 }
+
+pub mod attrs {
+    /// Has attributes.
+    #[derive(Clone, Debug, PartialEq)]
+    #[repr(C)]
+    #[non_exhaustive]
+    #[doc(alias = "thing")]
+    #[must_use]
+    #[allow(dead_code)]
+    pub struct Attributed {
+        pub field: u8,
+    }
+
+    #[deprecated(since = "1.2.0", note = "use something else")]
+    pub fn old() {}
+}
+
+pub mod deref {
+    pub struct Inner;
+
+    impl Inner {
+        pub fn inner_method(&self) {}
+    }
+
+    pub struct Middle(Inner);
+
+    impl Middle {
+        pub fn middle_method(&self) {}
+    }
+
+    impl std::ops::Deref for Middle {
+        type Target = Inner;
+        fn deref(&self) -> &Inner {
+            &self.0
+        }
+    }
+
+    pub struct Outer(Middle);
+
+    pub type Handle = Outer;
+
+    impl std::ops::Deref for Outer {
+        type Target = Middle;
+        fn deref(&self) -> &Middle {
+            &self.0
+        }
+    }
+
+    pub struct Name(String);
+
+    impl std::ops::Deref for Name {
+        type Target = str;
+        fn deref(&self) -> &str {
+            &self.0
+        }
+    }
+}
+
+pub mod macros {
+    /// Squares a number.
+    macro_rules! square {
+        ($x:expr) => {
+            $x * $x
+        };
+    }
+
+    pub fn nine() -> i32 {
+        square!(3)
+    }
+}
+
+pub mod traits {
+    pub trait Base {}
+
+    impl Base for u8 {}
+
+    pub trait Sub: Base + Clone {}
+
+    pub trait WhereSub
+    where
+        Self: Base,
+    {
+    }
+
+    pub trait ExternSub: externcrate::ExternTrait {}
+
+    pub trait Describe {
+        fn describe(&self) -> String;
+    }
+
+    impl<T: std::fmt::Display> Describe for T {
+        fn describe(&self) -> String {
+            format!("<{self}>")
+        }
+    }
+
+    /// Describes itself its own way.
+    pub struct Described;
+
+    impl std::fmt::Display for Described {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("described")
+        }
+    }
+
+    impl Described {
+        pub fn describe(&self) -> String {
+            "its own way".to_owned()
+        }
+    }
+
+    #[doc(hidden)]
+    pub trait Hidden {}
+
+    impl<T> Hidden for T {}
+
+    pub fn bounded<'a, T: Base + Clone, const N: usize>(_items: &'a [T; N], _f: impl Fn())
+    where
+        T: std::fmt::Debug,
+    {
+    }
+}
+
+pub mod unsafe_code {
+    pub struct Raw(*mut u8);
+
+    unsafe impl Send for Raw {}
+
+    pub unsafe trait Zeroable {}
+
+    unsafe impl Zeroable for Raw {}
+
+    impl Raw {
+        /// # Safety
+        /// The pointer must be valid.
+        pub unsafe fn read(&self) -> u8 {
+            *self.0
+        }
+
+        pub fn is_null(&self) -> bool {
+            self.0.is_null()
+        }
+    }
+
+    pub static mut COUNTER: u32 = 0;
+
+    pub static LIMIT: u32 = 10;
+
+    extern "C" {
+        pub fn abs(x: i32) -> i32;
+        pub static environ: *const *const u8;
+    }
+}
+
+pub mod links {
+    /// Goes with [`crate::x::S`] and [`Linked::go`], implements [`externcrate::ExternTrait`], and
+    /// holds a [`Vec`].
+    pub struct Linked(pub Vec<u8>);
+
+    impl Linked {
+        /// Back to [`Linked`].
+        pub fn go(&self) {}
+    }
+}
+
+pub mod sigs {
+    #[derive(Debug)]
+    pub struct ParseError;
+
+    pub fn parse_count(s: &str) -> Result<u32, ParseError> {
+        s.parse().map_err(|_| ParseError)
+    }
+
+    pub fn repeat(s: &str, times: usize) -> String {
+        s.repeat(times)
+    }
+
+    pub fn first<T: Clone>(items: &[T]) -> Option<T> {
+        items.first().cloned()
+    }
+}
+
+pub mod ctors {
+    use std::convert::{TryFrom, TryInto};
+    use std::str::FromStr;
+
+    #[derive(Debug, Default)]
+    pub struct Widget {
+        pub size: u32,
+    }
+
+    impl Widget {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        pub fn try_new(size: u32) -> Result<Widget, String> {
+            if size == 0 {
+                return Err("empty widget".to_owned());
+            }
+            Ok(Widget { size })
+        }
+
+        pub fn size(&self) -> u32 {
+            self.size
+        }
+    }
+
+    impl From<u32> for Widget {
+        fn from(size: u32) -> Self {
+            Widget { size }
+        }
+    }
+
+    impl From<crate::sigs::ParseError> for Widget {
+        fn from(_: crate::sigs::ParseError) -> Self {
+            Widget::default()
+        }
+    }
+
+    impl TryFrom<i64> for Widget {
+        type Error = std::num::TryFromIntError;
+
+        fn try_from(size: i64) -> Result<Self, Self::Error> {
+            Ok(Widget {
+                size: size.try_into()?,
+            })
+        }
+    }
+
+    impl From<Widget> for u32 {
+        fn from(widget: Widget) -> u32 {
+            widget.size
+        }
+    }
+
+    impl AsRef<u32> for Widget {
+        fn as_ref(&self) -> &u32 {
+            &self.size
+        }
+    }
+
+    impl FromStr for Widget {
+        type Err = std::num::ParseIntError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Ok(Widget { size: s.parse()? })
+        }
+    }
+
+    pub fn boxed() -> Box<Widget> {
+        Box::new(Widget::new())
+    }
+
+    pub fn maybe(size: Option<u32>) -> Option<Widget> {
+        size.map(Widget::from)
+    }
+}
+
+pub mod lookalike {
+    // Not a conversion, despite the name.
+    pub trait From<T> {}
+
+    impl From<String> for crate::ctors::Widget {}
+}
+
+pub mod pairs {
+    pub struct Pair<A, B> {
+        pub first: A,
+        pub second: B,
+    }
+
+    impl<A, B> Pair<A, B> {
+        pub fn swap(self) -> Pair<B, A> {
+            Pair {
+                first: self.second,
+                second: self.first,
+            }
+        }
+
+        pub fn first(&self) -> &A {
+            &self.first
+        }
+    }
+
+    pub struct Holder {
+        pub pair: Pair<u32, String>,
+        pub nested: Pair<Pair<u8, bool>, char>,
+    }
+}
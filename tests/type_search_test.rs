@@ -0,0 +1,71 @@
+#[macro_use]
+extern crate lazy_static;
+
+mod common;
+
+use common::ANALYSIS;
+use rsbrowse::type_search::{self, SignatureQuery};
+
+/// Paths and costs of the functions in testcrate matching the query.
+fn search(query: &str) -> Vec<(String, u32)> {
+    let query = query.parse::<SignatureQuery>().unwrap();
+    type_search::search(&ANALYSIS, &query)
+        .into_iter()
+        .filter(|m| m.path.starts_with("testcrate::"))
+        .map(|m| (m.path, m.cost))
+        .collect()
+}
+
+fn first(query: &str) -> (String, u32) {
+    search(query).into_iter().next().expect("nothing found")
+}
+
+#[test]
+fn exact() {
+    assert_eq!(
+        first("&str -> Result<_, ParseError>"),
+        ("testcrate::sigs::parse_count".to_owned(), 0)
+    );
+    // Paths are matched by their last part, and `Self` is the type a method is implemented on.
+    assert_eq!(
+        first("&unsafe_code::Raw -> u8"),
+        ("testcrate::unsafe_code::Raw::read".to_owned(), 0)
+    );
+    assert_eq!(
+        first("-> Reexported"),
+        ("testcrate::api::private::Reexported::new".to_owned(), 0)
+    );
+}
+
+#[test]
+fn inexact() {
+    // Arguments can be in any order.
+    assert_eq!(
+        search("usize, &str -> String"),
+        &[("testcrate::sigs::repeat".to_owned(), 1)]
+    );
+
+    // Generics match anything, but not as closely as the exact type. `Trait::method` takes
+    // `&self` of any type and returns its generic parameter.
+    assert_eq!(
+        search("&[u8] -> Option<u8>"),
+        &[
+            ("testcrate::sigs::first".to_owned(), 2),
+            ("testcrate::Trait::method".to_owned(), 4),
+        ]
+    );
+
+    // A return type can be matched inside a Result or Option.
+    assert_eq!(
+        first("&str -> u32"),
+        ("testcrate::sigs::parse_count".to_owned(), 3)
+    );
+
+    // Without a return type, anything can be returned.
+    assert_eq!(
+        first("&str, usize"),
+        ("testcrate::sigs::repeat".to_owned(), 0)
+    );
+
+    assert!(search("&str, &str, &str -> bool").is_empty());
+}